@@ -3,8 +3,9 @@ use std::time::Duration;
 use criterion::{
     criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion, PlotConfiguration,
 };
-use factorial::Factorial;
+use factorial::{DoubleFactorial, Factorial, FactorialContext};
 use num_bigint::*;
+use primal_sieve::Sieve;
 
 // To test the performance of the new implementation use
 // cargo bench --bench benchmark
@@ -58,8 +59,102 @@ fn bench_factorial(c: &mut Criterion) {
         });
     }
 
+    let x = 200000usize;
+    group.bench_with_input(BenchmarkId::new("Prime swing", x), &x, |b, x| {
+        b.iter(|| BigUint::from(*x).factorial())
+    });
+    let sieve = Sieve::new(x);
+    group.bench_with_input(BenchmarkId::new("Batched prime swing", x), &x, |b, x| {
+        b.iter(|| factorial::factorial_iterative(*x, &sieve))
+    });
+
+    group.finish()
+}
+
+fn bench_context(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Factorial context");
+    group.warm_up_time(Duration::new(1, 0));
+    group.measurement_time(Duration::new(10, 0));
+    group.sample_size(10);
+    for x in [1000usize, 3000, 5000] {
+        group.bench_with_input(BenchmarkId::new("Sieve per call", x), &x, |b, x| {
+            b.iter(|| {
+                for n in 1..=*x {
+                    BigUint::from(n).psw_factorial(&Sieve::new(n));
+                }
+            })
+        });
+        // Shares a thread-local sieve with the `thread-local-sieve` feature.
+        group.bench_with_input(BenchmarkId::new("checked_factorial", x), &x, |b, x| {
+            b.iter(|| {
+                for n in 1..=*x {
+                    BigUint::from(n).checked_factorial();
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("Shared sieve", x), &x, |b, x| {
+            b.iter(|| {
+                let mut context = FactorialContext::new();
+                for n in 1..=*x {
+                    context.factorial(&BigUint::from(n));
+                }
+            })
+        });
+    }
+    group.finish()
+}
+
+fn naive_double_factorial(n: usize) -> BigUint {
+    let mut acc = BigUint::from(1_usize);
+    let mut i = n;
+    while i > 1 {
+        acc *= i;
+        i -= 2;
+    }
+    acc
+}
+
+fn bench_double_factorial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Double factorial");
+    group.warm_up_time(Duration::new(1, 0));
+    group.measurement_time(Duration::new(10, 0));
+    group.sample_size(10);
+    for x in [49_999usize, 50_000] {
+        let sieve = Sieve::new(x);
+        group.bench_with_input(BenchmarkId::new("Naive", x), &x, |b, x| {
+            b.iter(|| naive_double_factorial(*x))
+        });
+        group.bench_with_input(BenchmarkId::new("Prime swing", x), &x, |b, x| {
+            b.iter(|| BigUint::from(*x).psw_double_factorial(&sieve))
+        });
+    }
+    group.finish()
+}
+
+fn bench_backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Big integer backends");
+    group.warm_up_time(Duration::new(1, 0));
+    group.measurement_time(Duration::new(20, 0));
+    group.sample_size(10);
+    let x = 200_000usize;
+    let sieve = Sieve::new(x);
+    // Both sides run the same batched prime swing, only the integers differ.
+    group.bench_with_input(BenchmarkId::new("num-bigint", x), &x, |b, x| {
+        b.iter(|| factorial::factorial_iterative(*x, &sieve))
+    });
+    // Run with `cargo bench --features ibig` to compare.
+    #[cfg(feature = "ibig")]
+    group.bench_with_input(BenchmarkId::new("ibig", x), &x, |b, x| {
+        b.iter(|| factorial::ubig_psw_factorial(*x, &sieve))
+    });
     group.finish()
 }
 
-criterion_group!(benches, bench_factorial);
+criterion_group!(
+    benches,
+    bench_factorial,
+    bench_context,
+    bench_double_factorial,
+    bench_backends
+);
 criterion_main!(benches);
@@ -0,0 +1,198 @@
+//! Bhargava's generalized factorials, defined through p-orderings of a set of
+//! integers.
+//!
+//! A p-ordering of a set `S` picks an arbitrary first element and then
+//! greedily, as the `k`-th element, one that minimizes the power of `p`
+//! dividing the product of its differences to the `k` elements before it.
+//! That minimal power `p^ν_k(S, p)` does not depend on the choices made, and
+//! the generalized factorial is `k!_S = ∏_p p^ν_k(S, p)`. For the integers
+//! themselves this is the ordinary `k!`.
+
+use crate::number_theory::legendre;
+use crate::{for_each_prime_power, prime_range};
+use num_bigint::BigUint;
+use num_traits::One;
+use primal_sieve::Sieve;
+use std::collections::BTreeSet;
+
+/// Returns the exponent of `p` in `x`, or `u32::MAX` for `x = 0`.
+fn valuation(mut x: u128, p: u64) -> u32 {
+    if x == 0 {
+        return u32::MAX;
+    }
+    let p = u128::from(p);
+    let mut exponent = 0;
+    while x.is_multiple_of(p) {
+        x /= p;
+        exponent += 1;
+    }
+    exponent
+}
+
+/// Returns the exponents `ν_0(S, p), ν_1(S, p), ...` of the p-sequence of the
+/// finite set `S` given by `set`, one for every distinct element.
+///
+/// The p-ordering is found greedily, which takes `O(|S|^2)` steps. Duplicate
+/// elements are ignored.
+///
+/// # Panics
+/// Panics if `p < 2`.
+///
+/// # Examples
+/// ```
+/// use factorial::p_sequence;
+/// // the exponents of 2 in 0!, 1!, ..., 5!
+/// assert_eq!(p_sequence(&[0, 1, 2, 3, 4, 5], 2), vec![0, 0, 1, 1, 3, 3]);
+/// ```
+pub fn p_sequence(set: &[i64], p: u64) -> Vec<u32> {
+    assert!(p >= 2, "p must be at least 2");
+    let mut remaining = set.to_vec();
+    remaining.sort_unstable();
+    remaining.dedup();
+    // The exponent of p in the product of differences to the chosen elements.
+    let mut exponents = vec![0u32; remaining.len()];
+    let mut sequence = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let (index, &exponent) = exponents
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, e)| e)
+            .expect("remaining is not empty");
+        sequence.push(exponent);
+        let chosen = remaining.swap_remove(index);
+        exponents.swap_remove(index);
+        for (&x, e) in remaining.iter().zip(&mut exponents) {
+            let difference = (i128::from(x) - i128::from(chosen)).unsigned_abs();
+            *e = e.saturating_add(valuation(difference, p));
+        }
+    }
+    sequence
+}
+
+/// Returns Bhargava's generalized factorial `k!_S` of the finite set `S`
+/// given by `set`, or `None` if `S` has `k` or fewer distinct elements.
+///
+/// A prime `p` only divides `k!_S` if `S` lies in at most `k` residue classes
+/// modulo `p`, so it divides a difference of any `k + 1` elements of `S`.
+/// These differences are factored by trial division, which takes up to the
+/// square root of the largest of them, and each prime found takes a
+/// p-ordering of `S`, so this is meant for small sets.
+///
+/// # Examples
+/// ```
+/// use factorial::bhargava_factorial;
+/// let squares: Vec<i64> = (0..20).map(|i| i * i).collect();
+/// // (2k)! / 2 for the squares
+/// assert_eq!(bhargava_factorial(&squares, 3), Some(360u32.into()));
+/// assert_eq!(bhargava_factorial(&[1, 2], 2), None);
+/// ```
+pub fn bhargava_factorial(set: &[i64], k: usize) -> Option<BigUint> {
+    let mut distinct = set.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if k >= distinct.len() {
+        return None;
+    }
+    let mut primes = BTreeSet::new();
+    for (i, &a) in distinct[..=k].iter().enumerate() {
+        for &b in &distinct[..i] {
+            for_each_prime_power(&a.abs_diff(b), |&p, _| {
+                primes.insert(p);
+            });
+        }
+    }
+    Some(primes.into_iter().fold(BigUint::one(), |acc, p| {
+        let exponent = p_sequence(&distinct, p)[k];
+        acc * num_traits::pow(BigUint::from(p), exponent as usize)
+    }))
+}
+
+/// Returns Bhargava's generalized factorial `n!_Z` of the integers, the
+/// specialization of [`bhargava_factorial`] to `S = Z`.
+///
+/// The natural order `0, 1, 2, ...` is a p-ordering of the integers for
+/// every prime `p`, and its p-sequence is the exponent of `p` in `n!` by
+/// Legendre's formula, so `n!_Z` is the product of these p-adic contributions
+/// and equals `n!`.
+///
+/// # Examples
+/// ```
+/// use factorial::bhargava_factorial_integers;
+/// assert_eq!(bhargava_factorial_integers(5), 120u32.into());
+/// ```
+pub fn bhargava_factorial_integers(n: usize) -> BigUint {
+    let sieve = Sieve::new(n.max(2));
+    prime_range(&sieve, 2, n).fold(BigUint::one(), |acc, p| {
+        acc * num_traits::pow(BigUint::from(p), legendre(n as u64, p as u64) as usize)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+
+    #[test]
+    fn integers_match_factorial() {
+        for n in 0..=200usize {
+            assert_eq!(
+                bhargava_factorial_integers(n),
+                BigUint::from(n).factorial(),
+                "mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn p_sequence_of_integers_is_legendre() {
+        let set: Vec<i64> = (0..=40).collect();
+        for p in [2u64, 3, 5, 7, 37] {
+            let expected: Vec<u32> = (0..=40).map(|k| legendre(k, p) as u32).collect();
+            assert_eq!(p_sequence(&set, p), expected, "mismatch for p = {p}");
+        }
+        // The p-sequence does not depend on the order or offset of the set.
+        let shuffled: Vec<i64> = (0..=40).map(|i| (i * 17) % 41 - 1000).collect();
+        assert_eq!(p_sequence(&shuffled, 3), p_sequence(&set, 3));
+    }
+
+    #[test]
+    fn generalized_factorials_of_known_sets() {
+        let integers: Vec<i64> = (-3..=8).collect();
+        let evens: Vec<i64> = (0..20).map(|i| 2 * i).collect();
+        let squares: Vec<i64> = (0..30).map(|i| i * i).collect();
+        for k in 0..=5u32 {
+            let factorial = BigUint::from(k).factorial();
+            assert_eq!(
+                bhargava_factorial(&integers, k as usize),
+                Some(factorial.clone())
+            );
+            assert_eq!(
+                bhargava_factorial(&evens, k as usize),
+                Some(&factorial << k as usize)
+            );
+            let expected = (BigUint::from(2 * k).factorial() / 2u32).max(BigUint::one());
+            assert_eq!(bhargava_factorial(&squares, k as usize), Some(expected));
+        }
+    }
+
+    #[test]
+    fn too_small_sets() {
+        assert_eq!(bhargava_factorial(&[], 0), None);
+        assert_eq!(bhargava_factorial(&[5, 5, 5], 1), None);
+        assert_eq!(bhargava_factorial(&[5, 5, 5], 0), Some(BigUint::one()));
+        assert_eq!(bhargava_factorial(&[0, 1], 1), Some(BigUint::one()));
+        assert_eq!(bhargava_factorial(&[0, 1], 2), None);
+    }
+
+    #[test]
+    fn wide_sets() {
+        assert_eq!(
+            bhargava_factorial(&[0, 1 << 40], 1),
+            Some(BigUint::one() << 40)
+        );
+        assert_eq!(
+            bhargava_factorial(&[i64::MIN, 0, i64::MAX], 1),
+            Some(BigUint::one())
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Prime swing specialized for arbitrary precision integers.
+//!
+//! Like the generic implementation behind [`Factorial`](crate::Factorial),
+//! which shares [`for_each_swing_factor`], the primes of every swing are
+//! collected in a machine word before they are multiplied into the result.
+//! Since the integers here can't overflow, this version also multiplies in
+//! place, replaces the recursion by a loop and applies the power of two with
+//! a shift.
+//!
+//! It is reached through [`factorial_iterative`] and [`factorial_into`], and
+//! the `ibig` backend shares it through [`BigNatural`].
+
+use crate::{array, prime_range, two_exponent};
+use core::ops::MulAssign;
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive};
+use primal_sieve::Sieve;
+
+/// The operations of an arbitrary precision natural number the batched
+/// prime swing needs.
+pub(crate) trait BigNatural: From<u128> + MulAssign + One {
+    /// Returns `self * self`.
+    fn square(&self) -> Self;
+}
+
+impl BigNatural for BigUint {
+    fn square(&self) -> Self {
+        self * self
+    }
+}
+
+/// Collects small factors in a `u64` until it would overflow.
+struct Accumulator<B> {
+    product: B,
+    batch: u64,
+}
+
+impl<B: BigNatural> Accumulator<B> {
+    fn new() -> Self {
+        Accumulator {
+            product: B::one(),
+            batch: 1,
+        }
+    }
+
+    fn push(&mut self, factor: usize) {
+        let factor = factor as u64;
+        match self.batch.checked_mul(factor) {
+            Some(batch) => self.batch = batch,
+            None => {
+                self.product *= B::from(u128::from(self.batch));
+                self.batch = factor;
+            }
+        }
+    }
+
+    fn finish(mut self) -> B {
+        self.product *= B::from(u128::from(self.batch));
+        self.product
+    }
+}
+
+/// Calls `push` with factors whose product is the odd part of the swing
+/// number `n! / (⌊n / 2⌋!)^2`, one odd prime power at a time.
+pub(crate) fn for_each_swing_factor(n: usize, sieve: &Sieve, mut push: impl FnMut(usize)) {
+    let sqrt = ((n as f64).sqrt().floor()) as usize;
+
+    for prime in prime_range(sieve, n / 2 + 1, n) {
+        push(prime);
+    }
+
+    for prime in prime_range(sieve, sqrt + 1, n / 3) {
+        if (n / prime) & 1 == 1 {
+            push(prime);
+        }
+    }
+
+    for prime in prime_range(sieve, 3, sqrt) {
+        let mut p = 1;
+        let mut q = n;
+        loop {
+            q /= prime;
+            if q == 0 {
+                break;
+            }
+            if q & 1 == 1 {
+                p *= prime;
+            }
+        }
+        if p > 1 {
+            push(p);
+        }
+    }
+}
+
+pub(crate) fn prime_swing<B: BigNatural>(n: usize, sieve: &Sieve) -> B {
+    if n < array::SMALL_ODD_SWING.len() {
+        return B::from(array::SMALL_ODD_SWING[n]);
+    }
+    let mut acc = Accumulator::new();
+    for_each_swing_factor(n, sieve, |factor| acc.push(factor));
+    acc.finish()
+}
+
+pub(crate) fn odd_factorial<B: BigNatural>(n: usize, sieve: &Sieve) -> B {
+    // Unrolls the recursion odd(n) = odd(n / 2)^2 * swing(n) by visiting
+    // n >> shift from the smallest value up, so no call stack is needed.
+    let mut res = B::one();
+    for shift in (0..usize::BITS - n.leading_zeros()).rev() {
+        let m = n >> shift;
+        if m < 2 {
+            continue;
+        }
+        res = res.square();
+        res *= prime_swing(m, sieve);
+    }
+    res
+}
+
+/// Returns `n!` using the prime swing algorithm with constant stack usage,
+/// or `None` if the sieve doesn't cover `n`.
+///
+/// Nothing in the algorithm recurses: the halvings of `n` are visited in a
+/// loop and the primes are multiplied into a single accumulator, so only the
+/// heap grows with `n`. This makes it safe to call on threads with a small
+/// stack.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_iterative;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// assert_eq!(factorial_iterative(10, &sieve), Some(3628800u32.into()));
+/// assert_eq!(factorial_iterative(100_000, &sieve), None);
+/// ```
+pub fn factorial_iterative(n: usize, sieve: &Sieve) -> Option<BigUint> {
+    if sieve.upper_bound() < n {
+        return None;
+    }
+    let mut res: BigUint = odd_factorial(n, sieve);
+    res <<= two_exponent(n as u64);
+    Some(res)
+}
+
+/// Multiplies `n!` into `acc`, returning `false` and leaving `acc` untouched
+/// if `n` doesn't fit in a `usize`.
+///
+/// Only the odd part of `n!` is computed, the power of two is shifted into
+/// `acc` directly, so `n!` itself is never allocated. This is convenient for
+/// building products of several factorials.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_into;
+/// use num_bigint::BigUint;
+/// let mut acc = BigUint::from(1u32);
+/// assert!(factorial_into(&3u32, &mut acc));
+/// assert!(factorial_into(&4u32, &mut acc));
+/// assert_eq!(acc, 144u32.into());
+/// ```
+pub fn factorial_into<T: ToPrimitive>(n: &T, acc: &mut BigUint) -> bool {
+    let Some(n) = n.to_usize() else {
+        return false;
+    };
+    let sieve = Sieve::new(n);
+    *acc *= odd_factorial::<BigUint>(n, &sieve);
+    *acc <<= two_exponent(n as u64);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrivateFactorial;
+
+    #[test]
+    fn matches_generic_path() {
+        let sieve = Sieve::new(5000);
+        for n in (0..300usize).chain([1000, 1234, 4999, 5000]) {
+            let big = BigUint::from(n);
+            assert_eq!(
+                prime_swing::<BigUint>(n, &sieve),
+                big.prime_swing(&sieve).unwrap(),
+                "swing mismatch for n = {n}"
+            );
+            assert_eq!(
+                odd_factorial::<BigUint>(n, &sieve),
+                big.odd_factorial(&sieve).unwrap(),
+                "odd factorial mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn iterative_on_small_stack() {
+        use crate::Factorial;
+        let n = 200_000;
+        let sieve = Sieve::new(n);
+        let res = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(move || factorial_iterative(n, &sieve))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(res, Some(BigUint::from(n).factorial()));
+    }
+
+    #[test]
+    fn into_accumulates_products() {
+        use crate::Factorial;
+        let mut acc = BigUint::one();
+        assert!(factorial_into(&3u32, &mut acc));
+        assert!(factorial_into(&4u32, &mut acc));
+        assert_eq!(acc, BigUint::from(144u32));
+        assert!(factorial_into(&1000u32, &mut acc));
+        assert!(factorial_into(&0u32, &mut acc));
+        assert_eq!(
+            acc,
+            BigUint::from(144u32) * BigUint::from(1000u32).factorial()
+        );
+    }
+
+    #[test]
+    fn into_rejects_huge_argument() {
+        let mut acc = BigUint::from(7u32);
+        let n = BigUint::one() << 200;
+        assert!(!factorial_into(&n, &mut acc));
+        assert_eq!(acc, BigUint::from(7u32));
+    }
+
+    #[test]
+    fn matches_naive() {
+        let sieve = Sieve::new(3000);
+        let mut expected = BigUint::one();
+        for n in 0..=3000usize {
+            if n > 0 {
+                expected *= n;
+            }
+            assert_eq!(factorial_iterative(n, &sieve), Some(expected.clone()));
+        }
+    }
+
+    #[test]
+    fn iterative_rejects_small_sieve() {
+        let sieve = Sieve::new(100);
+        let n = sieve.upper_bound();
+        assert!(factorial_iterative(n, &sieve).is_some());
+        assert_eq!(factorial_iterative(n + 1, &sieve), None);
+        assert_eq!(factorial_iterative(usize::MAX, &sieve), None);
+    }
+}
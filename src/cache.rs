@@ -0,0 +1,315 @@
+//! Caches that reuse previously computed factorials.
+
+use crate::{array, Factorial};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use primal_sieve::Sieve;
+use std::borrow::Cow;
+#[cfg(feature = "thread-local-sieve")]
+use std::cell::RefCell;
+#[cfg(feature = "thread-local-sieve")]
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// Arguments whose factorials are kept once they have been computed.
+const KNOWN_ARGUMENTS: [usize; 5] = [10, 100, 1_000, 10_000, 100_000];
+
+static KNOWN: [OnceLock<BigUint>; KNOWN_ARGUMENTS.len()] =
+    [const { OnceLock::new() }; KNOWN_ARGUMENTS.len()];
+
+static SMALL: [OnceLock<BigUint>; array::SMALL_FACTORIAL.len()] =
+    [const { OnceLock::new() }; array::SMALL_FACTORIAL.len()];
+
+/// Returns `n!`, borrowing it from a process-wide table for `n < 35`, where
+/// it fits in a `u128`.
+///
+/// Small factorials are converted to a `BigUint` only once, so workloads
+/// dominated by them don't allocate on every call. Larger factorials are
+/// computed and returned owned.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_cow;
+/// use std::borrow::Cow;
+/// assert!(matches!(factorial_cow(20), Cow::Borrowed(_)));
+/// assert!(matches!(factorial_cow(35), Cow::Owned(_)));
+/// ```
+pub fn factorial_cow(n: usize) -> Cow<'static, BigUint> {
+    match SMALL.get(n) {
+        Some(cell) => Cow::Borrowed(cell.get_or_init(|| BigUint::from(array::SMALL_FACTORIAL[n]))),
+        None => Cow::Owned(BigUint::from(n).factorial()),
+    }
+}
+
+/// Returns `n!`, borrowing it from a process-wide cache if `n` is a power of
+/// ten up to `100_000`.
+///
+/// A cached factorial is computed on the first request and shared by all
+/// later ones, other arguments are computed afresh on every call.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_or_known;
+/// use std::borrow::Cow;
+/// assert!(matches!(factorial_or_known(100), Cow::Borrowed(_)));
+/// assert_eq!(*factorial_or_known(5), 120u32.into());
+/// ```
+pub fn factorial_or_known(n: usize) -> Cow<'static, BigUint> {
+    match KNOWN_ARGUMENTS.iter().position(|&known| known == n) {
+        Some(i) => Cow::Borrowed(KNOWN[i].get_or_init(|| BigUint::from(n).factorial())),
+        None => Cow::Owned(BigUint::from(n).factorial()),
+    }
+}
+
+/// Computes factorials for a sequence of arguments, reusing the previous
+/// result.
+///
+/// Asking for `n!` after `m!` with `n >= m` only multiplies by
+/// `m + 1, ..., n`, so walking `n` upwards costs one multiplication per step.
+/// Smaller arguments are computed from scratch.
+///
+/// # Examples
+/// ```
+/// use factorial::IncrementalFactorial;
+/// let mut incremental = IncrementalFactorial::new();
+/// for n in 0..=10 {
+///     incremental.factorial(n);
+/// }
+/// assert_eq!(*incremental.factorial(11), 39916800u32.into());
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalFactorial {
+    n: usize,
+    value: BigUint,
+    #[cfg(test)]
+    multiplications: u64,
+}
+
+impl IncrementalFactorial {
+    /// Starts at `0! = 1`.
+    pub fn new() -> Self {
+        IncrementalFactorial {
+            n: 0,
+            value: BigUint::from(1u8),
+            #[cfg(test)]
+            multiplications: 0,
+        }
+    }
+
+    /// Returns `n!`, extending the last result if `n` isn't smaller than its
+    /// argument.
+    pub fn factorial(&mut self, n: usize) -> &BigUint {
+        if n < self.n {
+            self.value = BigUint::from(n).factorial();
+            self.n = n;
+        }
+        while self.n < n {
+            self.n += 1;
+            self.value *= self.n;
+            #[cfg(test)]
+            {
+                self.multiplications += 1;
+            }
+        }
+        &self.value
+    }
+}
+
+impl Default for IncrementalFactorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Computes factorials with one sieve that is shared between calls.
+///
+/// Without the `thread-local-sieve` feature [`Factorial::checked_factorial`]
+/// builds a new sieve for every argument, which dominates the cost of
+/// computing many moderately sized factorials; with it, the sieve is shared
+/// per thread and kept until the thread ends. The context keeps its sieve
+/// regardless of features, for as long as the context lives, and only
+/// rebuilds it, at least doubling its size, when an argument exceeds it.
+///
+/// # Examples
+/// ```
+/// use factorial::{Factorial, FactorialContext};
+/// use num_bigint::BigUint;
+/// let mut context = FactorialContext::new();
+/// for n in 1..=300u32 {
+///     let n = BigUint::from(n);
+///     assert_eq!(context.factorial(&n), Some(n.factorial()));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct FactorialContext {
+    sieve: Sieve,
+}
+
+impl FactorialContext {
+    /// Starts with a sieve that covers the arguments below `1000`.
+    pub fn new() -> Self {
+        Self::with_capacity(1000)
+    }
+
+    /// Starts with a sieve that covers the arguments up to `n`.
+    pub fn with_capacity(n: usize) -> Self {
+        FactorialContext {
+            sieve: Sieve::new(n),
+        }
+    }
+
+    /// Returns the largest argument the current sieve covers.
+    pub fn upper_bound(&self) -> usize {
+        self.sieve.upper_bound()
+    }
+
+    /// Returns `n!` if it doesn't overflow the type `T`, growing the sieve
+    /// first if it doesn't cover `n`.
+    pub fn factorial<T: Factorial + ToPrimitive>(&mut self, n: &T) -> Option<T> {
+        let bound = n.to_usize()?;
+        if bound > self.sieve.upper_bound() {
+            self.sieve = Sieve::new(bound.max(2 * self.sieve.upper_bound()));
+        }
+        n.psw_factorial(&self.sieve)
+    }
+
+    /// Returns `n!` with the current sieve, or `None` if it doesn't cover
+    /// `n` or `n!` overflows the type `T`.
+    pub fn psw_factorial<T: Factorial>(&self, n: &T) -> Option<T> {
+        n.psw_factorial(&self.sieve)
+    }
+}
+
+impl Default for FactorialContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "thread-local-sieve")]
+thread_local! {
+    /// The sieve behind `checked_factorial` on this thread. It sits behind an
+    /// `Rc` so no borrow is held while a factorial is computed with it.
+    static THREAD_SIEVE: RefCell<Rc<Sieve>> = RefCell::new(Rc::new(Sieve::new(1000)));
+}
+
+/// Calls `f` with this thread's sieve, growing it first if it doesn't cover
+/// `n`, the same way as [`FactorialContext::factorial`].
+#[cfg(feature = "thread-local-sieve")]
+pub(crate) fn with_thread_sieve<R>(n: usize, f: impl FnOnce(&Sieve) -> R) -> R {
+    let sieve = THREAD_SIEVE.with(|cell| {
+        let mut sieve = cell.borrow_mut();
+        if n > sieve.upper_bound() {
+            *sieve = Rc::new(Sieve::new(n.max(2 * sieve.upper_bound())));
+        }
+        Rc::clone(&sieve)
+    });
+    f(&sieve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "thread-local-sieve")]
+    #[test]
+    fn thread_sieve_is_reused() {
+        std::thread::spawn(|| {
+            let first = with_thread_sieve(5000, |sieve| sieve as *const Sieve);
+            assert_eq!(BigUint::from(3000u32).checked_factorial(), {
+                let sieve = Sieve::new(3000);
+                BigUint::from(3000u32).psw_factorial(&sieve)
+            });
+            let second = with_thread_sieve(4000, |sieve| {
+                assert!(sieve.upper_bound() >= 5000);
+                sieve as *const Sieve
+            });
+            assert_eq!(first, second);
+            let grown = with_thread_sieve(20_000, |sieve| {
+                assert!(sieve.upper_bound() >= 20_000);
+                sieve as *const Sieve
+            });
+            assert_ne!(first, grown);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn small_are_borrowed() {
+        for n in 0..array::SMALL_FACTORIAL.len() {
+            let first = factorial_cow(n);
+            let second = factorial_cow(n);
+            assert!(matches!(first, Cow::Borrowed(_)));
+            assert!(std::ptr::eq(first.as_ref(), second.as_ref()));
+            assert_eq!(*first, BigUint::from(n).factorial());
+        }
+    }
+
+    #[test]
+    fn large_are_owned() {
+        for n in [35usize, 100, 1000] {
+            let res = factorial_cow(n);
+            assert!(matches!(res, Cow::Owned(_)));
+            assert_eq!(*res, BigUint::from(n).factorial());
+        }
+    }
+
+    #[test]
+    fn incremental_walk_is_linear() {
+        let mut incremental = IncrementalFactorial::new();
+        for n in 1..=100usize {
+            assert_eq!(*incremental.factorial(n), BigUint::from(n).factorial());
+        }
+        assert_eq!(incremental.multiplications, 100);
+        // Asking again costs nothing.
+        incremental.factorial(100);
+        assert_eq!(incremental.multiplications, 100);
+    }
+
+    #[test]
+    fn incremental_going_back() {
+        let mut incremental = IncrementalFactorial::default();
+        assert_eq!(*incremental.factorial(50), BigUint::from(50u32).factorial());
+        assert_eq!(*incremental.factorial(20), BigUint::from(20u32).factorial());
+        assert_eq!(*incremental.factorial(21), BigUint::from(21u32).factorial());
+        assert_eq!(*incremental.factorial(0), BigUint::from(1u32));
+    }
+
+    #[test]
+    fn context_grows_on_demand() {
+        let mut context = FactorialContext::with_capacity(10);
+        let initial = context.upper_bound();
+        assert_eq!(context.psw_factorial(&5u32), Some(120));
+        let n = BigUint::from(5000u32);
+        assert_eq!(context.psw_factorial(&n), None);
+        assert_eq!(context.factorial(&n), Some(n.factorial()));
+        assert!(context.upper_bound() >= 5000);
+        assert!(context.upper_bound() >= 2 * initial);
+        // Smaller arguments don't shrink or rebuild the sieve.
+        let grown = context.upper_bound();
+        for n in (0..=5000u32).step_by(250) {
+            let n = BigUint::from(n);
+            assert_eq!(context.factorial(&n), Some(n.factorial()));
+        }
+        assert_eq!(context.upper_bound(), grown);
+        assert_eq!(context.factorial(&30u64), None);
+    }
+
+    #[test]
+    fn known_is_shared() {
+        let first = factorial_or_known(1000);
+        let second = factorial_or_known(1000);
+        assert!(matches!(first, Cow::Borrowed(_)));
+        assert!(std::ptr::eq(first.as_ref(), second.as_ref()));
+        assert_eq!(*first, BigUint::from(1000u32).factorial());
+    }
+
+    #[test]
+    fn unknown_is_computed() {
+        let res = factorial_or_known(999);
+        assert!(matches!(res, Cow::Owned(_)));
+        assert_eq!(*res, BigUint::from(999u32).factorial());
+        assert_eq!(*factorial_or_known(0), BigUint::from(1u32));
+    }
+}
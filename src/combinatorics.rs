@@ -0,0 +1,266 @@
+//! Combinatorial counting functions built on top of factorials.
+
+use crate::special::checked_subfactorial;
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedMul, One, Unsigned};
+use std::ops::{Mul, Sub};
+
+/// Returns the binomial coefficient `C(n, k)`, or `None` on overflow.
+///
+/// Uses the multiplicative formula so the running value always is the
+/// integer `C(n, i)`, which overflows much later than the factorials would.
+pub(crate) fn checked_binomial<T: PartialOrd + Unsigned + CheckedMul + Clone>(
+    n: &T,
+    k: &T,
+) -> Option<T> {
+    if k > n {
+        return Some(T::zero());
+    }
+    let n_minus_k = n.clone() - k.clone();
+    let k = if &n_minus_k < k { n_minus_k } else { k.clone() };
+    let mut acc = T::one();
+    let mut i = T::zero();
+    while i < k {
+        acc = acc.checked_mul(&(n.clone() - i.clone()))?;
+        i = i + T::one();
+        acc = acc / i.clone();
+    }
+    Some(acc)
+}
+
+/// Returns the number of multisets of size `k` drawn from `n` types,
+/// i.e. `C(n + k - 1, k)`, if it doesn't overflow the type `T`.
+///
+/// # Examples
+/// ```
+/// use factorial::combinations_with_repetition;
+/// assert_eq!(combinations_with_repetition(3u32, 2), Some(6));
+/// assert_eq!(combinations_with_repetition(0u32, 0), Some(1));
+/// ```
+pub fn combinations_with_repetition<T>(n: T, k: T) -> Option<T>
+where
+    T: PartialOrd + Unsigned + CheckedMul + CheckedAdd + Clone,
+{
+    if n.is_zero() {
+        return Some(if k.is_zero() { T::one() } else { T::zero() });
+    }
+    let top = n.checked_add(&k)? - T::one();
+    checked_binomial(&top, &k)
+}
+
+/// Returns the rencontres number `D(n, k)`, the number of permutations of
+/// `n` elements with exactly `k` fixed points, if it doesn't overflow the
+/// type `T`.
+///
+/// This is `C(n, k) * !(n - k)`, which generalizes the derangements
+/// `D(n, 0) = !n`.
+///
+/// # Examples
+/// ```
+/// use factorial::rencontres;
+/// assert_eq!(rencontres(4u32, 0), Some(9));
+/// assert_eq!(rencontres(4u32, 1), Some(8));
+/// assert_eq!(rencontres(4u32, 3), Some(0));
+/// ```
+pub fn rencontres<T>(n: T, k: T) -> Option<T>
+where
+    T: PartialOrd + Unsigned + CheckedMul + CheckedAdd + Clone,
+{
+    if k > n {
+        return Some(T::zero());
+    }
+    let derangements = checked_subfactorial(&(n.clone() - k.clone()))?;
+    checked_binomial(&n, &k)?.checked_mul(&derangements)
+}
+
+/// Returns the `k`-th Catalan number `C(2k, k) / (k + 1)`.
+fn catalan(k: u64) -> BigUint {
+    let k = BigUint::from(k);
+    checked_binomial(&(&k << 1), &k).expect("BigUint never overflows") / (k + 1u32)
+}
+
+/// Returns the `n`-th Motzkin number, the number of ways to draw
+/// non-crossing chords between `n` points on a circle.
+///
+/// It is evaluated as `sum_k C(n, 2k) C_k` over the Catalan numbers `C_k`.
+///
+/// # Examples
+/// ```
+/// use factorial::motzkin;
+/// assert_eq!(motzkin(6), 51u32.into());
+/// ```
+pub fn motzkin(n: u64) -> BigUint {
+    let n_big = BigUint::from(n);
+    (0..=n / 2)
+        .map(|k| {
+            let chords = checked_binomial(&n_big, &BigUint::from(2 * k));
+            chords.expect("BigUint never overflows") * catalan(k)
+        })
+        .sum()
+}
+
+/// Returns the falling factorial `x (x - 1) ... (x - k + 1)` for any ring
+/// element `x`, e.g. a square matrix, where `1` is the multiplicative
+/// identity.
+///
+/// The empty product for `k == 0` is the identity.
+///
+/// # Examples
+/// ```
+/// use factorial::falling_factorial_ring;
+/// assert_eq!(falling_factorial_ring(5i64, 3), 60);
+/// assert_eq!(falling_factorial_ring(2i64, 4), 0);
+/// assert_eq!(falling_factorial_ring(0.5f64, 2), -0.25);
+/// ```
+pub fn falling_factorial_ring<R>(x: R, k: usize) -> R
+where
+    R: Sub<Output = R> + Mul<Output = R> + One + Clone,
+{
+    let mut acc = R::one();
+    let mut term = x;
+    for _ in 0..k {
+        acc = acc * term.clone();
+        term = term - R::one();
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::*;
+
+    #[test]
+    fn binomial_small() {
+        assert_eq!(checked_binomial(&5u32, &2), Some(10));
+        assert_eq!(checked_binomial(&5u32, &0), Some(1));
+        assert_eq!(checked_binomial(&5u32, &5), Some(1));
+        assert_eq!(checked_binomial(&5u32, &6), Some(0));
+    }
+
+    #[test]
+    fn rencontres_sum_to_factorial() {
+        use crate::Factorial;
+        for n in 0..=20u64 {
+            let sum: u64 = (0..=n).map(|k| rencontres(n, k).unwrap()).sum();
+            assert_eq!(sum, n.factorial(), "mismatch for n = {n}");
+        }
+    }
+
+    #[test]
+    fn rencontres_small() {
+        assert_eq!(rencontres(4u32, 0), Some(9));
+        assert_eq!(rencontres(4u32, 4), Some(1));
+        assert_eq!(rencontres(4u32, 5), Some(0));
+        assert_eq!(rencontres(30u32, 0), None);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Matrix([[i64; 2]; 2]);
+
+    impl Mul for Matrix {
+        type Output = Matrix;
+
+        fn mul(self, rhs: Matrix) -> Matrix {
+            let (a, b) = (self.0, rhs.0);
+            Matrix([
+                [
+                    a[0][0] * b[0][0] + a[0][1] * b[1][0],
+                    a[0][0] * b[0][1] + a[0][1] * b[1][1],
+                ],
+                [
+                    a[1][0] * b[0][0] + a[1][1] * b[1][0],
+                    a[1][0] * b[0][1] + a[1][1] * b[1][1],
+                ],
+            ])
+        }
+    }
+
+    impl Sub for Matrix {
+        type Output = Matrix;
+
+        fn sub(self, rhs: Matrix) -> Matrix {
+            let (a, b) = (self.0, rhs.0);
+            Matrix([
+                [a[0][0] - b[0][0], a[0][1] - b[0][1]],
+                [a[1][0] - b[1][0], a[1][1] - b[1][1]],
+            ])
+        }
+    }
+
+    impl One for Matrix {
+        fn one() -> Matrix {
+            Matrix([[1, 0], [0, 1]])
+        }
+    }
+
+    #[test]
+    fn falling_factorial_matrix() {
+        let x = Matrix([[1, 2], [3, 4]]);
+        let identity = Matrix::one();
+        assert_eq!(falling_factorial_ring(x, 0), identity);
+        assert_eq!(falling_factorial_ring(x, 1), x);
+        // X (X - I) (X - 2I) = X^3 - 3X^2 + 2X
+        //                   = [[37, 54], [81, 118]] - [[21, 30], [45, 66]] + [[2, 4], [6, 8]]
+        assert_eq!(falling_factorial_ring(x, 3), Matrix([[18, 28], [42, 60]]));
+    }
+
+    #[test]
+    fn falling_factorial_integers() {
+        for n in 0..10i64 {
+            for k in 0..=n as usize {
+                let expected = (n - k as i64 + 1..=n).product::<i64>();
+                assert_eq!(falling_factorial_ring(n, k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn multiset_three_choose_two() {
+        assert_eq!(combinations_with_repetition(3u32, 2), Some(6));
+    }
+
+    #[test]
+    fn multiset_matches_binomial() {
+        for n in 1..20u64 {
+            for k in 0..20u64 {
+                assert_eq!(
+                    combinations_with_repetition(n, k),
+                    checked_binomial(&(n + k - 1), &k),
+                    "mismatch for n = {n}, k = {k}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn multiset_empty_types() {
+        assert_eq!(combinations_with_repetition(0u32, 3), Some(0));
+    }
+
+    #[test]
+    fn multiset_biguint() {
+        let n = 100.to_biguint().unwrap();
+        let k = 50.to_biguint().unwrap();
+        let top = 149.to_biguint().unwrap();
+        assert_eq!(
+            combinations_with_repetition(n, k.clone()),
+            checked_binomial(&top, &k)
+        );
+    }
+
+    #[test]
+    fn motzkin_numbers() {
+        let expected = [1u32, 1, 2, 4, 9, 21, 51, 127, 323, 835, 2188];
+        for (n, &m) in expected.iter().enumerate() {
+            assert_eq!(motzkin(n as u64), BigUint::from(m), "mismatch for n = {n}");
+        }
+        // The recurrence (n + 2) M_n = (2n + 1) M_(n - 1) + (3n - 3) M_(n - 2)
+        for n in 2..100u64 {
+            assert_eq!(
+                (n + 2) * motzkin(n),
+                (2 * n + 1) * motzkin(n - 1) + (3 * n - 3) * motzkin(n - 2)
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Factorials that are only computed on request.
+
+use crate::estimate::factorial_digit_count;
+use crate::number_theory::legendre;
+use crate::Factorial;
+use num_bigint::BigUint;
+
+/// A factorial that has not been computed yet.
+///
+/// Cheap metadata is available right away, but the value itself is only
+/// computed by an explicit call to [`DeferredFactorial::compute`]. This makes
+/// it harder to accidentally compute something like `1_000_000!` in a hot
+/// path.
+///
+/// # Examples
+/// ```
+/// use factorial::DeferredFactorial;
+/// let deferred = DeferredFactorial::new(1_000_000);
+/// assert_eq!(deferred.digit_count(), 5_565_709);
+/// assert_eq!(deferred.trailing_zeros(), 249_998);
+/// assert_eq!(DeferredFactorial::new(5).compute(), 120u32.into());
+/// ```
+#[must_use = "the factorial is only computed by calling `compute`"]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DeferredFactorial {
+    n: u64,
+}
+
+impl DeferredFactorial {
+    /// Defers the computation of `n!`.
+    pub fn new(n: u64) -> Self {
+        DeferredFactorial { n }
+    }
+
+    /// Returns the argument `n`.
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the number of decimal digits of `n!` without computing it.
+    pub fn digit_count(&self) -> u64 {
+        factorial_digit_count(self.n)
+    }
+
+    /// Returns the number of trailing zeros of `n!` in base ten without
+    /// computing it.
+    pub fn trailing_zeros(&self) -> u64 {
+        legendre(self.n, 5)
+    }
+
+    /// Computes `n!`.
+    pub fn compute(&self) -> BigUint {
+        BigUint::from(self.n).factorial()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_without_compute() {
+        let deferred = DeferredFactorial::new(1_000_000_000_000);
+        assert_eq!(deferred.n(), 1_000_000_000_000);
+        assert_eq!(deferred.trailing_zeros(), 249_999_999_997);
+        assert!(deferred.digit_count() > 11_000_000_000_000);
+    }
+
+    #[test]
+    fn compute_matches_factorial() {
+        for n in [0u64, 1, 10, 34, 35, 200, 1000] {
+            let deferred = DeferredFactorial::new(n);
+            let value = deferred.compute();
+            assert_eq!(value, BigUint::from(n).factorial());
+            assert_eq!(deferred.digit_count(), value.to_string().len() as u64);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! The reasons a factorial can't be computed.
+
+use core::fmt;
+
+/// Why [`Factorial::try_factorial`](crate::Factorial::try_factorial) and
+/// friends couldn't compute a factorial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FactorialError {
+    /// The factorial doesn't fit in the target type
+    Overflow,
+    /// The argument is negative, where the factorial is undefined
+    Negative,
+    /// The sieve doesn't cover the argument of the prime swing algorithm
+    SieveTooSmall {
+        /// The argument, the least upper bound that would do
+        needed: usize,
+        /// The upper bound the sieve covers
+        have: usize,
+    },
+}
+
+impl fmt::Display for FactorialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FactorialError::Overflow => f.write_str("overflow computing factorial"),
+            FactorialError::Negative => f.write_str("factorial of negative number is undefined"),
+            FactorialError::SieveTooSmall { needed, have } => {
+                write!(
+                    f,
+                    "sieve up to {have} is too small for the factorial of {needed}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FactorialError {}
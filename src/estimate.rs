@@ -0,0 +1,452 @@
+//! Cheap estimates of the size of `n!` that avoid computing it.
+
+use crate::number_theory::legendre;
+use crate::{array, Factorial};
+use num_bigint::{BigInt, BigUint};
+use num_traits::FromPrimitive;
+use std::f64::consts::{E, LN_10, LN_2, PI};
+
+/// Returns `ln(n!)` using Stirling's series for arguments beyond the table.
+pub(crate) fn ln_factorial(n: u64) -> f64 {
+    if n < array::SMALL_FACTORIAL.len() as u64 {
+        return (array::SMALL_FACTORIAL[n as usize] as f64).ln();
+    }
+    stirling_series(n as f64)
+}
+
+/// Returns `ln(n!)` from Stirling's series with three correction terms, which
+/// is accurate to the precision of an `f64` for `n >= 35`.
+pub(crate) fn stirling_series(n: f64) -> f64 {
+    let n2 = n * n;
+    n * n.ln() - n + 0.5 * (2.0 * PI * n).ln() + 1.0 / (12.0 * n) - 1.0 / (360.0 * n * n2)
+        + 1.0 / (1260.0 * n * n2 * n2)
+}
+
+/// The Bernoulli numbers `B_2, B_4, ..., B_20`.
+const BERNOULLI: [f64; 10] = [
+    1.0 / 6.0,
+    -1.0 / 30.0,
+    1.0 / 42.0,
+    -1.0 / 30.0,
+    5.0 / 66.0,
+    -691.0 / 2730.0,
+    7.0 / 6.0,
+    -3617.0 / 510.0,
+    43867.0 / 798.0,
+    -174611.0 / 330.0,
+];
+
+/// Returns `ln(n!)` using Stirling's series with `terms` correction terms
+/// `B_2k / (2k (2k - 1) n^(2k - 1))`, at most 10.
+///
+/// The error is smaller than the first omitted term. With `terms = 0` only
+/// `n ln n - n + ln(2πn) / 2` is used, which is off by about `1 / (12 n)`;
+/// each further term gains roughly a factor `n^2`. For `n = 10` that is an
+/// absolute error of about `3e-6` with one term, `8e-9` with two, `6e-11`
+/// with three and below the precision of `f64` from five terms on. The
+/// series is asymptotic, so for very small `n` more terms are not always
+/// better: for `n = 1` the error is smallest with three terms (`3e-4`).
+///
+/// # Examples
+/// ```
+/// use factorial::log_factorial_stieltjes;
+/// let exact = 3628800f64.ln();
+/// assert!((log_factorial_stieltjes(10, 3) - exact).abs() < 1e-10);
+/// ```
+pub fn log_factorial_stieltjes(n: u64, terms: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let n = n as f64;
+    let mut sum = n * n.ln() - n + 0.5 * (2.0 * PI * n).ln();
+    let mut power = n;
+    for (k, b) in (1..).zip(BERNOULLI.iter().take(terms)) {
+        let k = k as f64;
+        sum += b / (2.0 * k * (2.0 * k - 1.0) * power);
+        power *= n * n;
+    }
+    sum
+}
+
+/// Largest `n` for which Stirling's approximation of `n!` is finite as an
+/// `f64`.
+const MAX_STIRLING_F64: u64 = 170;
+
+/// Returns `n! - round(sqrt(2πn) (n / e)^n)`, the exact error of Stirling's
+/// approximation.
+///
+/// The approximation is evaluated in `f64`, so only about the leading 16
+/// digits of the difference are meaningful. As Stirling's formula
+/// underestimates the factorial by a factor of about `1 + 1 / (12n)`, the
+/// error is around `n! / (12n)` and never negative.
+///
+/// # Panics
+/// Panics if `n > 170`, where the approximation overflows an `f64`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_error_vs_stirling;
+/// // 5! = 120, Stirling gives 118.019...
+/// assert_eq!(factorial_error_vs_stirling(5), 2.into());
+/// ```
+pub fn factorial_error_vs_stirling(n: u64) -> BigInt {
+    assert!(
+        n <= MAX_STIRLING_F64,
+        "Stirling's approximation of {n}! overflows an f64"
+    );
+    let n_f = n as f64;
+    let stirling = (2.0 * PI * n_f).sqrt() * (n_f / E).powi(n as i32);
+    let stirling = BigInt::from_f64(stirling.round()).expect("the approximation is finite");
+    BigInt::from(BigUint::from(n).factorial()) - stirling
+}
+
+/// Returns `n!` from Stirling's formula with three correction terms,
+/// `sqrt(2πn) (n / e)^n (1 + 1/(12n) + 1/(288n^2) - 139/(51840n^3))`, or
+/// infinity beyond `170!`.
+pub(crate) fn stirling_approx(n: u64) -> f64 {
+    if n == 0 {
+        return 1.0;
+    }
+    if n > MAX_STIRLING_F64 {
+        return f64::INFINITY;
+    }
+    let n = n as f64;
+    let correction = 1.0 + 1.0 / (12.0 * n) + 1.0 / (288.0 * n * n) - 139.0 / (51840.0 * n * n * n);
+    (2.0 * PI * n).sqrt() * (n / E).powf(n) * correction
+}
+
+/// Returns the number of bits of `n!`.
+///
+/// For `n` in the range of the precomputed table the result is exact, beyond
+/// it is derived from Stirling's series without computing the factorial.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_bit_length;
+/// // 10! = 3628800 < 2^22
+/// assert_eq!(factorial_bit_length(10), 22);
+/// ```
+pub fn factorial_bit_length(n: u64) -> u64 {
+    if n < array::SMALL_FACTORIAL.len() as u64 {
+        return u64::from(128 - array::SMALL_FACTORIAL[n as usize].leading_zeros());
+    }
+    (ln_factorial(n) / LN_2).floor() as u64 + 1
+}
+
+/// Returns the number of bytes needed to store `n!`, i.e.
+/// `ceil(factorial_bit_length(n) / 8)`.
+///
+/// This can be used to decide whether computing the factorial is feasible at
+/// all before allocating anything.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_byte_size;
+/// assert_eq!(factorial_byte_size(10), 3);
+/// ```
+pub fn factorial_byte_size(n: u64) -> usize {
+    factorial_bit_length(n).div_ceil(8) as usize
+}
+
+/// Factorials with at most this many decimal digits are included exactly in a
+/// [`FactorialSummary`].
+pub const SUMMARY_EXACT_DIGITS: u64 = 10_000;
+
+/// An overview of `n!` that is cheap to compute even when `n!` itself is not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FactorialSummary {
+    /// `n!` in scientific notation with seven significant digits, e.g.
+    /// `"3.628800e6"`
+    pub approx_scientific: String,
+    /// The number of decimal digits of `n!`
+    pub digit_count: u64,
+    /// The number of trailing zeros of `n!` in base ten
+    pub trailing_zeros: usize,
+    /// `n!` itself, if it has at most [`SUMMARY_EXACT_DIGITS`] digits
+    pub exact: Option<BigUint>,
+}
+
+/// Returns the scientific notation of `n!` with seven significant digits.
+fn scientific(n: u64) -> String {
+    if n < array::SMALL_FACTORIAL.len() as u64 {
+        return format!("{:.6e}", array::SMALL_FACTORIAL[n as usize] as f64);
+    }
+    let log10 = ln_factorial(n) / LN_10;
+    let exponent = log10.floor();
+    // Formatting the mantissa takes care of rounding it up to 10.
+    let mantissa = format!("{:.6e}", 10f64.powf(log10 - exponent));
+    let (digits, mantissa_exponent) = mantissa.split_once('e').unwrap();
+    let exponent = exponent as u64 + mantissa_exponent.parse::<u64>().unwrap();
+    format!("{digits}e{exponent}")
+}
+
+/// Returns a [`FactorialSummary`] of `n!`.
+///
+/// The exact value is only computed if it has at most
+/// [`SUMMARY_EXACT_DIGITS`] digits, all other fields are derived without
+/// computing `n!`. For large `n` only the first few digits of the scientific
+/// notation are accurate.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_summary;
+/// let summary = factorial_summary(10);
+/// assert_eq!(summary.approx_scientific, "3.628800e6");
+/// assert_eq!(summary.digit_count, 7);
+/// assert_eq!(summary.trailing_zeros, 2);
+/// assert_eq!(summary.exact, Some(3628800u32.into()));
+/// ```
+pub fn factorial_summary(n: u64) -> FactorialSummary {
+    let digit_count = factorial_digit_count(n);
+    FactorialSummary {
+        approx_scientific: scientific(n),
+        digit_count,
+        trailing_zeros: legendre(n, 5) as usize,
+        exact: if digit_count <= SUMMARY_EXACT_DIGITS {
+            BigUint::from(n).checked_factorial()
+        } else {
+            None
+        },
+    }
+}
+
+/// Returns `n!` if it takes at most `max_bytes` bytes, and `None` otherwise.
+///
+/// The size is checked with [`factorial_byte_size`] before anything is
+/// computed, so unreasonably large requests are rejected without allocating.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_with_limit;
+/// assert_eq!(factorial_with_limit(10, 3), Some(3628800u32.into()));
+/// assert_eq!(factorial_with_limit(10, 2), None);
+/// ```
+pub fn factorial_with_limit(n: u64, max_bytes: usize) -> Option<BigUint> {
+    if factorial_byte_size(n) > max_bytes {
+        return None;
+    }
+    BigUint::from(n).checked_factorial()
+}
+
+/// Returns the number of decimal digits of `n!`.
+///
+/// Beyond the precomputed table this is Kamenetsky's formula, refined with
+/// the correction terms of Stirling's series.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_digit_count;
+/// assert_eq!(factorial_digit_count(10), 7);
+/// assert_eq!(factorial_digit_count(100), 158);
+/// ```
+pub fn factorial_digit_count(n: u64) -> u64 {
+    if n < array::SMALL_FACTORIAL.len() as u64 {
+        return array::SMALL_FACTORIAL[n as usize].ilog10() as u64 + 1;
+    }
+    (ln_factorial(n) / LN_10).floor() as u64 + 1
+}
+
+/// Returns the smallest `n` such that `n!` has at least `digits` decimal
+/// digits.
+///
+/// # Examples
+/// ```
+/// use factorial::smallest_n_with_digits;
+/// // 99! has 156 digits, 100! has 158.
+/// assert_eq!(smallest_n_with_digits(157), 100);
+/// assert_eq!(smallest_n_with_digits(158), 100);
+/// ```
+pub fn smallest_n_with_digits(digits: u64) -> usize {
+    let mut high = 1usize;
+    while factorial_digit_count(high as u64) < digits {
+        high *= 2;
+    }
+    let mut low = 0usize;
+    // Invariant: `high!` has enough digits, `low!` might not.
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if factorial_digit_count(mid as u64) >= digits {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn bit_length_matches_exact() {
+        for n in (0..=300).chain([500, 1000, 1234, 4000, 10000]) {
+            assert_eq!(
+                factorial_bit_length(n),
+                BigUint::from(n).factorial().bits(),
+                "mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn digit_count_matches_exact() {
+        for n in (0..=300).chain([1000, 3000]) {
+            assert_eq!(
+                factorial_digit_count(n),
+                BigUint::from(n).factorial().to_string().len() as u64,
+                "mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn smallest_with_digits() {
+        assert_eq!(smallest_n_with_digits(0), 0);
+        assert_eq!(smallest_n_with_digits(1), 0);
+        assert_eq!(smallest_n_with_digits(2), 4);
+        assert_eq!(smallest_n_with_digits(158), 100);
+        assert_eq!(factorial_digit_count(99), 156);
+        for digits in [10, 100, 1000, 10000, 35660] {
+            let n = smallest_n_with_digits(digits) as u64;
+            assert!(factorial_digit_count(n) >= digits);
+            assert!(factorial_digit_count(n - 1) < digits);
+        }
+    }
+
+    #[test]
+    fn limit_allows_small() {
+        assert_eq!(
+            factorial_with_limit(100, 1024),
+            Some(BigUint::from(100u32).factorial())
+        );
+        let size = factorial_byte_size(1000);
+        assert!(factorial_with_limit(1000, size).is_some());
+        assert!(factorial_with_limit(1000, size - 1).is_none());
+    }
+
+    #[test]
+    fn limit_rejects_huge_before_allocating() {
+        // Computing this factorial would need terabytes of memory.
+        assert_eq!(factorial_with_limit(1_000_000_000_000, 1 << 20), None);
+    }
+
+    #[test]
+    fn summary_small() {
+        let summary = factorial_summary(25);
+        let exact = BigUint::from(25u32).factorial();
+        assert_eq!(summary.approx_scientific, "1.551121e25");
+        assert_eq!(summary.digit_count, 26);
+        assert_eq!(summary.trailing_zeros, 6);
+        assert_eq!(summary.exact, Some(exact));
+        assert_eq!(factorial_summary(0).approx_scientific, "1.000000e0");
+    }
+
+    #[test]
+    fn summary_matches_exact() {
+        for n in [35u64, 100, 1000, 3000] {
+            let summary = factorial_summary(n);
+            let exact = BigUint::from(n).factorial().to_string();
+            let zeros = exact.len() - exact.trim_end_matches('0').len();
+            assert_eq!(summary.digit_count, exact.len() as u64);
+            assert_eq!(summary.trailing_zeros, zeros);
+            assert_eq!(&summary.approx_scientific[..1], &exact[..1]);
+            assert_eq!(&summary.approx_scientific[2..6], &exact[1..5]);
+            let exponent = summary.approx_scientific.split_once('e').unwrap().1;
+            assert_eq!(exponent, (exact.len() - 1).to_string());
+            assert_eq!(summary.exact.unwrap().to_string(), exact);
+        }
+    }
+
+    #[test]
+    fn summary_huge() {
+        let summary = factorial_summary(1_000_000_000);
+        assert!(summary.approx_scientific.starts_with("9.904"));
+        assert!(summary.approx_scientific.ends_with("e8565705522"));
+        assert_eq!(summary.digit_count, 8_565_705_523);
+        assert_eq!(summary.trailing_zeros, 249_999_998);
+        assert_eq!(summary.exact, None);
+    }
+
+    /// Returns the natural logarithm of `x`, even if it exceeds the range of
+    /// `f64`.
+    fn ln_biguint(x: &BigUint) -> f64 {
+        use num_traits::ToPrimitive;
+        let shift = x.bits().saturating_sub(64);
+        (x >> shift).to_f64().unwrap().ln() + shift as f64 * LN_2
+    }
+
+    #[test]
+    fn stirling_error_small_values() {
+        assert_eq!(factorial_error_vs_stirling(0), BigInt::from(1));
+        // 1! and 2! are rounded up from 0.922... and 1.919...
+        assert_eq!(factorial_error_vs_stirling(1), BigInt::from(0));
+        assert_eq!(factorial_error_vs_stirling(2), BigInt::from(0));
+        assert_eq!(factorial_error_vs_stirling(5), BigInt::from(2));
+        // 10! = 3628800, Stirling gives 3598695.6...
+        assert_eq!(factorial_error_vs_stirling(10), BigInt::from(30104));
+    }
+
+    #[test]
+    fn stirling_error_relative_size() {
+        use num_traits::{Signed, ToPrimitive};
+        for n in [10u64, 50, 100, 170] {
+            let error = factorial_error_vs_stirling(n);
+            assert!(error.is_positive(), "Stirling overestimates {n}!");
+            let exact = BigInt::from(BigUint::from(n).factorial());
+            let relative = error.to_f64().unwrap() / exact.to_f64().unwrap();
+            // n! / stirling = 1 + 1 / (12n) + ..., so the relative error is
+            // a little below 1 / (12n).
+            let expected = 1.0 / (12.0 * n as f64);
+            assert!(relative < expected, "n = {n}: {relative}");
+            assert!(relative > 0.9 * expected, "n = {n}: {relative}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn stirling_error_too_large() {
+        factorial_error_vs_stirling(171);
+    }
+
+    #[test]
+    fn stieltjes_error_bounded_by_next_term() {
+        let mut exact = BigUint::from(1u32);
+        for n in 1..=100u64 {
+            exact *= n;
+            let exact_ln = ln_biguint(&exact);
+            for (terms, b) in BERNOULLI.iter().enumerate() {
+                let k = terms as f64 + 1.0;
+                let next = (b / (2.0 * k * (2.0 * k - 1.0))).abs() / (n as f64).powf(2.0 * k - 1.0);
+                let err = (log_factorial_stieltjes(n, terms) - exact_ln).abs();
+                assert!(
+                    err <= next + 1e-13 * exact_ln.max(1.0),
+                    "n = {n}, terms = {terms}: error {err} exceeds {next}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stieltjes_more_terms_help() {
+        let exact = ln_biguint(&BigUint::from(20u32).factorial());
+        let errors: Vec<f64> = (0..4)
+            .map(|terms| (log_factorial_stieltjes(20, terms) - exact).abs())
+            .collect();
+        assert!(errors.windows(2).all(|w| w[1] < w[0]));
+        assert_eq!(log_factorial_stieltjes(0, 3), 0.0);
+    }
+
+    #[test]
+    fn byte_size_matches_exact() {
+        for n in [0u64, 1, 5, 10, 34, 35, 100, 171, 999, 2048, 10000] {
+            assert_eq!(
+                factorial_byte_size(n),
+                BigUint::from(n).factorial().to_bytes_be().len(),
+                "mismatch for n = {n}"
+            );
+        }
+    }
+}
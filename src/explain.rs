@@ -0,0 +1,136 @@
+//! Reporting which algorithm computes a factorial.
+
+use crate::{Factorial, PrivateFactorial, SMALL_SWING_LEN};
+use num_traits::{CheckedMul, FromPrimitive, ToPrimitive, Unsigned};
+use std::fmt;
+use std::ops::Shl;
+
+/// The way [`Factorial::checked_factorial`] computes a factorial.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FactorialPath {
+    /// Multiplying `2 * 3 * ... * n`, for types too small for the tables
+    Naive,
+    /// Looking up and combining the precomputed tables
+    Table,
+    /// The prime swing algorithm with a sieve of this size
+    PrimeSwing {
+        /// The upper bound of the sieve: that of the thread's shared sieve
+        /// with the `thread-local-sieve` feature, otherwise the argument a
+        /// new sieve was created with. It saturates at `usize::MAX` if the
+        /// argument doesn't fit a `usize`, in which case no factorial is
+        /// computed.
+        sieve_size: usize,
+    },
+}
+
+impl fmt::Display for FactorialPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FactorialPath::Naive => f.write_str("naive product"),
+            FactorialPath::Table => f.write_str("table lookup"),
+            FactorialPath::PrimeSwing { sieve_size } => {
+                write!(f, "prime swing with sieve of size {sieve_size}")
+            }
+        }
+    }
+}
+
+/// Returns `n!`, if it doesn't overflow the type `T`, together with the path
+/// [`Factorial::checked_factorial`] takes to compute it.
+///
+/// This makes the performance characteristics of individual arguments
+/// visible: table lookups are nearly free, while the prime swing needs a
+/// sieve of at least size `n`. With the `thread-local-sieve` feature that
+/// sieve is only built when the thread's shared one is too small, otherwise
+/// every call builds its own.
+///
+/// # Examples
+/// ```
+/// use factorial::{factorial_explain, FactorialPath};
+/// let (value, path) = factorial_explain(&10u64);
+/// assert_eq!(value, Some(3628800));
+/// assert_eq!(path.to_string(), "table lookup");
+/// let (_, path) = factorial_explain(&num_bigint::BigUint::from(200u32));
+/// assert!(matches!(path, FactorialPath::PrimeSwing { sieve_size } if sieve_size >= 200));
+/// ```
+pub fn factorial_explain<T>(n: &T) -> (Option<T>, FactorialPath)
+where
+    T: PartialOrd
+        + Unsigned
+        + CheckedMul
+        + Clone
+        + FromPrimitive
+        + ToPrimitive
+        + Shl<u32, Output = T>,
+{
+    let Some(table_len) = T::from_usize(SMALL_SWING_LEN) else {
+        return (n.naive_factorial(), FactorialPath::Naive);
+    };
+    if n < &table_len {
+        return (n.psw_factorial_with_array(), FactorialPath::Table);
+    }
+    let Some(sieve_size) = n.to_usize() else {
+        let sieve_size = usize::MAX;
+        return (None, FactorialPath::PrimeSwing { sieve_size });
+    };
+    #[cfg(feature = "thread-local-sieve")]
+    let (value, sieve_size) = crate::cache::with_thread_sieve(sieve_size, |sieve| {
+        (n.psw_factorial(sieve), sieve.upper_bound())
+    });
+    #[cfg(not(feature = "thread-local-sieve"))]
+    let value = n.psw_factorial(&primal_sieve::Sieve::new(sieve_size));
+    (value, FactorialPath::PrimeSwing { sieve_size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn small_use_tables() {
+        for n in [0u64, 1, 20, SMALL_SWING_LEN as u64 - 1] {
+            let (value, path) = factorial_explain(&n);
+            assert_eq!(value, n.checked_factorial());
+            assert_eq!(path, FactorialPath::Table);
+            assert_eq!(path.to_string(), "table lookup");
+        }
+        assert_eq!(factorial_explain(&5u8), (Some(120), FactorialPath::Table));
+    }
+
+    /// The size of the sieve `checked_factorial` uses for `n`.
+    fn sieve_size_for(n: usize) -> usize {
+        #[cfg(feature = "thread-local-sieve")]
+        return crate::cache::with_thread_sieve(n, |sieve| sieve.upper_bound());
+        #[cfg(not(feature = "thread-local-sieve"))]
+        return n;
+    }
+
+    #[test]
+    fn large_use_sieve() {
+        for n in [SMALL_SWING_LEN, 1000, 5000] {
+            let big = BigUint::from(n);
+            let (value, path) = factorial_explain(&big);
+            assert_eq!(value, big.checked_factorial());
+            let sieve_size = sieve_size_for(n);
+            assert!(sieve_size >= n);
+            assert_eq!(path, FactorialPath::PrimeSwing { sieve_size });
+            assert_eq!(
+                path.to_string(),
+                format!("prime swing with sieve of size {sieve_size}")
+            );
+        }
+        let (value, path) = factorial_explain(&200u128);
+        assert_eq!(value, None);
+        let sieve_size = sieve_size_for(200);
+        assert_eq!(path, FactorialPath::PrimeSwing { sieve_size });
+        let (value, path) = factorial_explain(&u128::MAX);
+        assert_eq!(value, None);
+        assert_eq!(
+            path,
+            FactorialPath::PrimeSwing {
+                sieve_size: usize::MAX
+            }
+        );
+    }
+}
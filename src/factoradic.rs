@@ -0,0 +1,111 @@
+//! The factorial number system, in which the digit at position `i` has radix
+//! `i + 1` and place value `i!`.
+//!
+//! Every non-negative integer has exactly one such representation, which
+//! makes it the natural way to rank permutations: the digits are the Lehmer
+//! code.
+
+/// Returns the factoradic digits of `n`, least significant first, so the
+/// digit at position `i` is at most `i`.
+///
+/// The first digit is always `0`, and zero is written as the single digit
+/// `0`.
+///
+/// # Examples
+/// ```
+/// use factorial::to_factoradic;
+/// // 463 = 3 * 5! + 4 * 4! + 1 * 3! + 0 * 2! + 1 * 1! + 0 * 0!
+/// assert_eq!(to_factoradic(463), vec![0, 1, 0, 1, 4, 3]);
+/// ```
+pub fn to_factoradic(mut n: u128) -> Vec<u8> {
+    let mut digits = vec![0];
+    let mut radix = 2;
+    while n > 0 {
+        digits.push((n % radix) as u8);
+        n /= radix;
+        radix += 1;
+    }
+    digits
+}
+
+/// Returns the number with the given factoradic digits, least significant
+/// first, or `None` if a digit exceeds its position or the number doesn't
+/// fit in a `u128`.
+///
+/// # Examples
+/// ```
+/// use factorial::from_factoradic;
+/// assert_eq!(from_factoradic(&[0, 1, 0, 1, 4, 3]), Some(463));
+/// assert_eq!(from_factoradic(&[0, 2]), None);
+/// ```
+pub fn from_factoradic(digits: &[u8]) -> Option<u128> {
+    let mut n: u128 = 0;
+    // i!, or None once it no longer fits, which is fine for zero digits.
+    let mut place = Some(1u128);
+    for (i, &digit) in digits.iter().enumerate() {
+        if usize::from(digit) > i {
+            return None;
+        }
+        if digit > 0 {
+            n = n.checked_add(place?.checked_mul(u128::from(digit))?)?;
+        }
+        place = place.and_then(|place| place.checked_mul(i as u128 + 1));
+    }
+    Some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_numbers() {
+        assert_eq!(to_factoradic(0), vec![0]);
+        assert_eq!(to_factoradic(1), vec![0, 1]);
+        assert_eq!(to_factoradic(5), vec![0, 1, 2]);
+        assert_eq!(to_factoradic(6), vec![0, 0, 0, 1]);
+        assert_eq!(from_factoradic(&[]), Some(0));
+        assert_eq!(from_factoradic(&[0, 0, 0, 0]), Some(0));
+        for n in 0..5000 {
+            let digits = to_factoradic(n);
+            assert!(digits.iter().enumerate().all(|(i, &d)| usize::from(d) <= i));
+            assert_eq!(from_factoradic(&digits), Some(n));
+        }
+    }
+
+    #[test]
+    fn round_trip_extremes() {
+        let mut n = u128::MAX;
+        for _ in 0..200 {
+            assert_eq!(from_factoradic(&to_factoradic(n)), Some(n));
+            n = n / 3 * 2 + 1;
+        }
+        // 34! is the largest factorial in a u128.
+        let factorial_34 = (1..=34u128).product::<u128>();
+        let mut digits = vec![0; 35];
+        digits[34] = 1;
+        assert_eq!(to_factoradic(factorial_34), digits);
+        assert_eq!(from_factoradic(&digits), Some(factorial_34));
+    }
+
+    #[test]
+    fn invalid_digits() {
+        assert_eq!(from_factoradic(&[1]), None);
+        assert_eq!(from_factoradic(&[0, 1, 3]), None);
+        // Valid digits, but 35! doesn't fit.
+        let mut digits = vec![0; 36];
+        digits[35] = 1;
+        assert_eq!(from_factoradic(&digits), None);
+        // u128::MAX plus i!.
+        let mut digits = to_factoradic(u128::MAX);
+        let i = (1..digits.len())
+            .find(|&i| usize::from(digits[i]) < i)
+            .unwrap();
+        digits[i] += 1;
+        assert_eq!(from_factoradic(&digits), None);
+        // Leading zeros beyond the range of a u128 are harmless.
+        let mut digits = to_factoradic(u128::MAX);
+        digits.resize(100, 0);
+        assert_eq!(from_factoradic(&digits), Some(u128::MAX));
+    }
+}
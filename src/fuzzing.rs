@@ -0,0 +1,73 @@
+//! Support for generating factorial arguments with `arbitrary`.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Arguments just below and above the limits of the primitive types and of
+/// the precomputed tables.
+const BOUNDARY_ARGUMENTS: [usize; 14] = [0, 1, 2, 5, 6, 12, 13, 20, 21, 34, 35, 128, 129, 130];
+
+/// A factorial argument for property testing and fuzzing.
+///
+/// Generated values are biased towards small arguments and the boundaries
+/// where the primitive types overflow, and never exceed
+/// [`FactorialArg::MAX`], so computing their factorial is always cheap.
+///
+/// # Examples
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use factorial::{Factorial, FactorialArg};
+/// let mut u = Unstructured::new(&[2, 9, 4, 7]);
+/// let FactorialArg(n) = FactorialArg::arbitrary(&mut u).unwrap();
+/// assert!(n <= FactorialArg::MAX);
+/// let _ = (n as u64).checked_factorial();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FactorialArg(pub usize);
+
+impl FactorialArg {
+    /// The largest argument that is generated.
+    pub const MAX: usize = 10_000;
+}
+
+impl<'a> Arbitrary<'a> for FactorialArg {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let arg = match u.int_in_range(0..=3u8)? {
+            0 | 1 => u.int_in_range(0..=20)?,
+            2 => *u.choose(&BOUNDARY_ARGUMENTS)?,
+            _ => u.int_in_range(0..=Self::MAX)?,
+        };
+        Ok(FactorialArg(arg))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(1 + std::mem::size_of::<usize>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn arbitrary_arguments_compute() {
+        let data: Vec<u8> = (0..=255u8).cycle().step_by(7).take(512).collect();
+        let mut u = Unstructured::new(&data);
+        let mut seen = 0;
+        while !u.is_empty() {
+            let FactorialArg(n) = FactorialArg::arbitrary(&mut u).unwrap();
+            assert!(n <= FactorialArg::MAX);
+            let expected: BigUint = (1..=n).map(BigUint::from).product();
+            assert_eq!(BigUint::from(n).factorial(), expected);
+            seen += 1;
+        }
+        assert!(seen > 10);
+    }
+
+    #[test]
+    fn arbitrary_empty_input_is_zero() {
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(FactorialArg::arbitrary(&mut u).unwrap(), FactorialArg(0));
+    }
+}
@@ -0,0 +1,547 @@
+//! Floating point approximations of the factorial for real arguments.
+
+use std::f64::consts::{E, PI};
+
+/// Returns `x!`, i.e. `Γ(x + 1)`, using Spouge's approximation with `a` terms.
+///
+/// The relative error is bounded by `a^(-1/2) (2π)^(-(a + 1/2))`, so every
+/// extra term buys a little under one decimal digit of accuracy: `a = 5`
+/// gives about 5 digits, `a = 12` about 11. Because the coefficients
+/// alternate in sign and grow quickly, cancellation in `f64` limits the
+/// attainable accuracy to about 12 digits, which is reached around `a = 12`;
+/// beyond `a = 15` the rounding errors start to dominate and the result gets
+/// worse again.
+///
+/// The approximation is valid for `x > -1`, for other arguments `NaN` is
+/// returned.
+///
+/// # Panics
+/// Panics if `a == 0`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_spouge;
+/// assert!((factorial_spouge(5.0, 12) - 120.0).abs() < 1e-8);
+/// ```
+pub fn factorial_spouge(x: f64, a: usize) -> f64 {
+    assert!(a > 0, "Spouge's approximation needs at least one term");
+    if x.is_nan() || x <= -1.0 {
+        return f64::NAN;
+    }
+    ln_factorial_spouge(x, a).exp()
+}
+
+/// Returns `ln(x!)` for `x > -1` using Spouge's approximation with `a` terms.
+fn ln_factorial_spouge(x: f64, a: usize) -> f64 {
+    let a_f = a as f64;
+    let mut sum = (2.0 * PI).sqrt();
+    // (k - 1)! for the current k
+    let mut k_fact = 1.0;
+    for k in 1..a {
+        let k_f = k as f64;
+        let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+        let c_k = sign / k_fact * (a_f - k_f).powf(k_f - 0.5) * E.powf(a_f - k_f);
+        sum += c_k / (x + k_f);
+        k_fact *= k_f;
+    }
+    (x + 0.5) * (x + a_f).ln() - (x + a_f) + sum.ln()
+}
+
+/// Coefficients of the Lanczos approximation with `g = 7` and nine terms.
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// From this argument on [`gamma`] uses Stirling's series.
+const STIRLING_THRESHOLD: f64 = 10.0;
+
+/// Largest argument for which `Γ(x)` is finite as an `f64`.
+const MAX_GAMMA_ARGUMENT: f64 = 171.624_376_956_302_7;
+
+/// Returns the Lanczos sum and `t = x + 6.5` for `x >= 0.5`, from which
+/// `Γ(x) = sqrt(2π) t^(x - 1/2) e^(-t) sum`.
+fn lanczos_sum(x: f64) -> (f64, f64) {
+    let x = x - 1.0;
+    let sum = LANCZOS_COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS_COEFFICIENTS[0], |acc, (i, c)| {
+            acc + c / (x + i as f64 + 1.0)
+        });
+    (sum, x + 7.5)
+}
+
+/// Returns `Γ(x)` for `x >= 0.5` using the Lanczos approximation.
+fn gamma_lanczos(x: f64) -> f64 {
+    let (sum, t) = lanczos_sum(x);
+    (2.0 * PI).sqrt() * t.powf(x - 0.5) * (-t).exp() * sum
+}
+
+/// Returns `1/(12x) - 1/(360x^3) + ...`, the correction in Stirling's series
+/// `ln Γ(x) = (x - 1/2) ln x - x + ln(2π) / 2 + ...`.
+fn stirling_series(x: f64) -> f64 {
+    let inv = 1.0 / x;
+    let inv2 = inv * inv;
+    // B_2k / (2k (2k - 1)) for k = 1..=6, evaluated with Horner's scheme
+    inv * (1.0 / 12.0
+        - inv2
+            * (1.0 / 360.0
+                - inv2
+                    * (1.0 / 1260.0
+                        - inv2 * (1.0 / 1680.0 - inv2 * (1.0 / 1188.0 - inv2 * 691.0 / 360360.0)))))
+}
+
+/// Returns `Γ(x)` for large `x` using Stirling's series
+/// `sqrt(2π / x) (x / e)^x exp(1/(12x) - 1/(360x^3) + ...)`.
+fn gamma_stirling(x: f64) -> f64 {
+    if x > MAX_GAMMA_ARGUMENT {
+        return f64::INFINITY;
+    }
+    let series = stirling_series(x);
+    // Split x^(x - 1/2) in halves so nothing overflows before the result.
+    let half_power = x.powf(0.5 * (x - 0.5));
+    (2.0 * PI).sqrt() * half_power * (half_power * (-x).exp()) * series.exp()
+}
+
+/// Returns `Γ(x)` for any real `x`, choosing the method by the argument.
+///
+/// Moderate arguments use the Lanczos approximation, which is accurate to
+/// about 15 digits there. From `x = 10` on Stirling's asymptotic series is
+/// both faster and as accurate, to within the rounding of the final power,
+/// and it returns infinity without a detour once `Γ(x)` exceeds `f64::MAX`
+/// beyond `x ≈ 171.62`. Arguments below `1/2` use the reflection formula
+/// `Γ(x) Γ(1 - x) = π / sin(πx)`.
+///
+/// This is the recommended gamma function of the crate; non-positive
+/// integers are poles, for which `NaN` is returned.
+///
+/// # Examples
+/// ```
+/// use factorial::gamma;
+/// assert!((gamma(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-15);
+/// assert!((gamma(11.0) / 3628800.0 - 1.0).abs() < 1e-14);
+/// assert_eq!(gamma(200.0), f64::INFINITY);
+/// ```
+pub fn gamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x < 0.5 {
+        if x == x.floor() {
+            return f64::NAN;
+        }
+        return PI / ((PI * x).sin() * gamma(1.0 - x));
+    }
+    if x >= STIRLING_THRESHOLD {
+        gamma_stirling(x)
+    } else {
+        gamma_lanczos(x)
+    }
+}
+
+/// Returns `ln Γ(x)` for `x >= 0.5`, with the same methods as [`gamma`] but
+/// without overflow for large arguments.
+fn ln_gamma_positive(x: f64) -> f64 {
+    if x >= STIRLING_THRESHOLD {
+        (x - 0.5) * x.ln() - x + 0.5 * (2.0 * PI).ln() + stirling_series(x)
+    } else {
+        let (sum, t) = lanczos_sum(x);
+        0.5 * (2.0 * PI).ln() + (x - 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
+/// Returns `(ln |Γ(x)|, sign(Γ(x)))`, or `NaN` at the poles.
+///
+/// This is the logarithmic counterpart of [`gamma`], reflecting arguments
+/// below `1/2` the same way.
+fn ln_gamma(x: f64) -> (f64, f64) {
+    if x.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+    if x >= 0.5 {
+        return (ln_gamma_positive(x), 1.0);
+    }
+    if x == x.floor() {
+        return (f64::NAN, f64::NAN);
+    }
+    let sin = (PI * x).sin();
+    let ln = PI.ln() - sin.abs().ln() - ln_gamma_positive(1.0 - x);
+    (ln, sin.signum())
+}
+
+/// Returns `Γ(a) / Γ(b)` for any real `a` and `b`.
+///
+/// The ratio is computed from the logarithms of the gamma functions, so it is
+/// finite whenever the ratio itself is, even if `Γ(a)` and `Γ(b)` overflow.
+/// For positive integers this is `(a - 1)! / (b - 1)!`.
+///
+/// # Examples
+/// ```
+/// use factorial::gamma_ratio;
+/// assert!((gamma_ratio(5.0, 3.0) - 12.0).abs() < 1e-9);
+/// ```
+pub fn gamma_ratio(a: f64, b: f64) -> f64 {
+    let (ln_a, sign_a) = ln_gamma(a);
+    let (ln_b, sign_b) = ln_gamma(b);
+    sign_a * sign_b * (ln_a - ln_b).exp()
+}
+
+/// Returns the Beta function `B(a, b) = Γ(a) Γ(b) / Γ(a + b)`.
+///
+/// It is computed from the logarithms of the gamma functions for stability.
+/// For positive integers it equals `(a - 1)! (b - 1)! / (a + b - 1)!`.
+///
+/// # Examples
+/// ```
+/// use factorial::beta;
+/// // 1! 2! / 4! = 1 / 12
+/// assert!((beta(2.0, 3.0) - 1.0 / 12.0).abs() < 1e-12);
+/// assert!((beta(0.5, 0.5) - std::f64::consts::PI).abs() < 1e-10);
+/// ```
+pub fn beta(a: f64, b: f64) -> f64 {
+    let (ln_a, sign_a) = ln_gamma(a);
+    let (ln_b, sign_b) = ln_gamma(b);
+    let (ln_ab, sign_ab) = ln_gamma(a + b);
+    sign_a * sign_b * sign_ab * (ln_a + ln_b - ln_ab).exp()
+}
+
+/// Returns the digamma function `ψ(x) = Γ'(x) / Γ(x)`, the logarithmic
+/// derivative of the gamma function.
+///
+/// Small arguments are shifted up with `ψ(x) = ψ(x + 1) - 1/x` until the
+/// asymptotic expansion `ln x - 1/(2x) - Σ B_2k / (2k x^2k)` is accurate,
+/// negative arguments use the reflection formula
+/// `ψ(1 - x) - ψ(x) = π cot(πx)`.
+///
+/// Non-positive integers are poles, for which `NaN` is returned.
+///
+/// # Examples
+/// ```
+/// use factorial::digamma;
+/// // ψ(1) = -γ
+/// assert!((digamma(1.0) + 0.577_215_664_901_532_9).abs() < 1e-14);
+/// ```
+pub fn digamma(x: f64) -> f64 {
+    if x.is_nan() || (x <= 0.0 && x == x.floor()) {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        return digamma(1.0 - x) - PI / (PI * x).tan();
+    }
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 10.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let inv2 = 1.0 / (x * x);
+    // B_2k / 2k for k = 1..=6, evaluated with Horner's scheme in 1/x^2
+    let series = inv2
+        * (1.0 / 12.0
+            - inv2
+                * (1.0 / 120.0
+                    - inv2
+                        * (1.0 / 252.0
+                            - inv2
+                                * (1.0 / 240.0 - inv2 * (1.0 / 132.0 - inv2 * 691.0 / 32760.0)))));
+    result + x.ln() - 0.5 / x - series
+}
+
+/// The argument `x ≈ 0.4616` at which `x!` is smallest for `x > -1`, and
+/// its value there.
+const FACTORIAL_MINIMUM: (f64, f64) = (0.461_632_144_968_362_3, 0.885_603_194_410_888_7);
+
+/// Returns the real `x` with `x! = Γ(x + 1) = y` on the increasing branch
+/// `x >= 0.4616...`, or `None` if `y` is below the minimum `0.8856...` of
+/// the factorial there or isn't finite.
+///
+/// The solution is found with Newton's method on `ln Γ(x + 1) - ln y`,
+/// whose derivative is the [`digamma`] function. As the logarithm of the
+/// gamma function is convex, starting to the right of the solution converges
+/// monotonically.
+///
+/// # Examples
+/// ```
+/// use factorial::inverse_factorial_f64;
+/// assert!((inverse_factorial_f64(120.0).unwrap() - 5.0).abs() < 1e-9);
+/// assert_eq!(inverse_factorial_f64(0.5), None);
+/// ```
+pub fn inverse_factorial_f64(y: f64) -> Option<f64> {
+    let (x_min, y_min) = FACTORIAL_MINIMUM;
+    if !y.is_finite() || y < y_min {
+        return None;
+    }
+    let target = y.ln();
+    let mut x: f64 = 2.0;
+    while ln_gamma(x + 1.0).0 < target {
+        x *= 2.0;
+    }
+    for _ in 0..100 {
+        let step = (ln_gamma(x + 1.0).0 - target) / digamma(x + 1.0);
+        // Close to the minimum the derivative vanishes, don't overshoot it.
+        let next = (x - step).max(x_min);
+        if (next - x).abs() <= 1e-15 * x {
+            return Some(next);
+        }
+        x = next;
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel_err(approx: f64, exact: f64) -> f64 {
+        ((approx - exact) / exact).abs()
+    }
+
+    fn bound(a: usize) -> f64 {
+        let a = a as f64;
+        a.powf(-0.5) * (2.0 * PI).powf(-(a + 0.5))
+    }
+
+    #[test]
+    fn gamma_reference_values() {
+        // Γ(x) rounded to the precision of an f64.
+        let reference = [
+            (0.1, 9.513_507_698_668_73),
+            (0.5, 1.772_453_850_905_516),
+            (1.5, 0.886_226_925_452_758),
+            (3.7, 4.170_651_783_796_604),
+            (7.25, 1_155.381_013_919_989_7),
+            (9.99, 354_802.017_019_831_1),
+            (10.0, 362_880.0),
+            (10.5, 1_133_278.388_948_785_6),
+            (25.3, 1.622_777_117_670_876_6e24),
+            (50.5, 4.290_462_912_351_96e63),
+            (100.5, 9.320_963_104_082_717e156),
+            (150.25, 1.332_150_776_195_163_5e261),
+            (170.5, 5.562_092_414_56e305),
+            (171.6, 1.585_896_909_667_256_5e308),
+            (-0.5, -3.544_907_701_811_032),
+            (-2.5, -0.945_308_720_482_941_9),
+            (-7.3, 4.183_878_730_135_48e-4),
+        ];
+        for (x, expected) in reference {
+            let err = rel_err(gamma(x), expected);
+            assert!(err < 1e-13, "x = {x}: relative error {err}");
+        }
+    }
+
+    #[test]
+    fn gamma_methods_agree_at_threshold() {
+        for x in [9.5, 9.9, 10.0, 10.1, 12.0] {
+            let err = rel_err(gamma_stirling(x), gamma_lanczos(x));
+            assert!(err < 1e-14, "x = {x}: relative error {err}");
+        }
+    }
+
+    #[test]
+    fn gamma_edge_cases() {
+        assert!(gamma(0.0).is_nan());
+        assert!(gamma(-3.0).is_nan());
+        assert!(gamma(f64::NAN).is_nan());
+        assert_eq!(gamma(171.7), f64::INFINITY);
+        assert_eq!(gamma(1e10), f64::INFINITY);
+        assert_eq!(gamma(f64::INFINITY), f64::INFINITY);
+        let mut factorial = 1.0;
+        for n in 1..=30 {
+            assert!(rel_err(gamma(n as f64), factorial) < 1e-14, "n = {n}");
+            factorial *= n as f64;
+        }
+    }
+
+    #[test]
+    fn spouge_integers() {
+        let mut exact = 1.0;
+        for n in 0..20 {
+            if n > 0 {
+                exact *= n as f64;
+            }
+            for a in [3, 5, 8, 12] {
+                let err = rel_err(factorial_spouge(n as f64, a), exact);
+                assert!(
+                    err <= bound(a) + 1e-12,
+                    "n = {n}, a = {a}: relative error {err}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spouge_half_integers() {
+        // (n - 1/2)! = Γ(n + 1/2) = (2n)! / (4^n n!) √π
+        let mut exact = PI.sqrt();
+        for n in 0..15 {
+            if n > 0 {
+                exact *= n as f64 - 0.5;
+            }
+            let x = n as f64 - 0.5;
+            let mut last_err = f64::INFINITY;
+            for a in [3, 6, 9, 12] {
+                let err = rel_err(factorial_spouge(x, a), exact);
+                assert!(err <= bound(a) + 1e-12, "x = {x}, a = {a}: {err}");
+                assert!(err < last_err || err < 1e-12);
+                last_err = err;
+            }
+        }
+    }
+
+    #[test]
+    fn gamma_negative_half_integers() {
+        // Γ(1/2 - n) = (-4)^n n! / (2n)! √π
+        let mut expected = PI.sqrt();
+        for n in 1..10 {
+            let n_f = n as f64;
+            expected *= -1.0 / (n_f - 0.5);
+            let x = 0.5 - n_f;
+            let err = rel_err(gamma(x), expected);
+            assert!(err < 1e-11, "x = {x}: relative error {err}");
+        }
+        assert!(rel_err(gamma(-0.5), -2.0 * PI.sqrt()) < 1e-11);
+    }
+
+    #[test]
+    fn ln_gamma_matches_gamma() {
+        for i in -40..=340 {
+            let x = i as f64 * 0.5 + 0.25;
+            let (ln, sign) = ln_gamma(x);
+            let expected = gamma(x);
+            assert_eq!(sign, expected.signum(), "x = {x}");
+            // A few units in the last place of the logarithm.
+            let scale = expected.abs().ln().abs().max(1.0);
+            let err = (ln - expected.abs().ln()).abs() / scale;
+            assert!(err < 1e-14, "x = {x}: relative error {err}");
+        }
+        // Far beyond the range of Γ itself: ln Γ(1001) = ln(1000!)
+        let ln_1000 = crate::estimate::ln_factorial(1000);
+        assert!(rel_err(ln_gamma(1001.0).0, ln_1000) < 1e-15);
+    }
+
+    #[test]
+    fn ratio_integers() {
+        assert!(rel_err(gamma_ratio(5.0, 3.0), 12.0) < 1e-14);
+        assert!(rel_err(gamma_ratio(3.0, 5.0), 1.0 / 12.0) < 1e-14);
+        assert!(rel_err(gamma_ratio(7.5, 7.5), 1.0) < 1e-14);
+    }
+
+    #[test]
+    fn ratio_beyond_overflow() {
+        // Γ(400) and Γ(399) overflow, their ratio is 399.
+        assert!(rel_err(gamma_ratio(400.0, 399.0), 399.0) < 1e-9);
+        assert!(rel_err(gamma_ratio(1000.5, 1000.0), 31.618_824_001_838_69) < 1e-9);
+    }
+
+    #[test]
+    fn ratio_negative_arguments() {
+        // Γ(-1/2) / Γ(1/2) = -2
+        assert!(rel_err(gamma_ratio(-0.5, 0.5), -2.0) < 1e-11);
+        // Γ(-3/2) / Γ(-1/2) = 1 / (-3/2)
+        assert!(rel_err(gamma_ratio(-1.5, -0.5), -2.0 / 3.0) < 1e-11);
+        assert!(gamma_ratio(-2.0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn beta_integers() {
+        use crate::ratio::factorial_ratio_rational;
+        use num_traits::ToPrimitive;
+        for m in 1..30usize {
+            for n in 1..30usize {
+                // (m - 1)! (n - 1)! / (m + n - 1)!
+                let exact =
+                    factorial_ratio_rational(m - 1, m + n - 1) * factorial_ratio_rational(n - 1, 0);
+                let exact = exact.to_f64().unwrap();
+                let err = rel_err(beta(m as f64, n as f64), exact);
+                assert!(err < 1e-13, "m = {m}, n = {n}: relative error {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn beta_half_integers() {
+        assert!(rel_err(beta(0.5, 0.5), PI) < 1e-14);
+        assert!(rel_err(beta(1.5, 0.5), PI / 2.0) < 1e-14);
+        assert!(rel_err(beta(2.5, 1.5), PI / 16.0) < 1e-14);
+        assert!(rel_err(beta(-0.5, 1.0), -2.0) < 1e-14);
+    }
+
+    #[test]
+    fn digamma_known_values() {
+        const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+        assert!((digamma(1.0) + EULER_GAMMA).abs() < 1e-14);
+        // ψ(1/2) = -γ - 2 ln 2
+        let half = -EULER_GAMMA - 2.0 * std::f64::consts::LN_2;
+        assert!(rel_err(digamma(0.5), half) < 1e-14);
+        // ψ(n + 1) = H_n - γ
+        let mut harmonic = 0.0;
+        for n in 1..50 {
+            harmonic += 1.0 / n as f64;
+            let err = rel_err(digamma(n as f64 + 1.0), harmonic - EULER_GAMMA);
+            assert!(err < 1e-14, "n = {n}: relative error {err}");
+        }
+    }
+
+    #[test]
+    fn digamma_negative_arguments() {
+        // ψ(-1/2) = ψ(1/2) + 2
+        assert!(rel_err(digamma(-0.5), digamma(0.5) + 2.0) < 1e-13);
+        assert!(rel_err(digamma(-2.5), digamma(0.5) + 2.0 + 2.0 / 3.0 + 0.4) < 1e-13);
+        assert!(digamma(0.0).is_nan());
+        assert!(digamma(-4.0).is_nan());
+    }
+
+    #[test]
+    fn digamma_derivative_of_ln_gamma() {
+        for x in [0.3, 1.7, 4.2, 25.0, 300.0] {
+            let h = 1e-5;
+            let numeric = (ln_gamma(x + h).0 - ln_gamma(x - h).0) / (2.0 * h);
+            assert!(rel_err(digamma(x), numeric) < 1e-6, "x = {x}");
+        }
+    }
+
+    #[test]
+    fn inverse_factorial_integers() {
+        let mut y = 1.0;
+        for n in 1..=170 {
+            y *= n as f64;
+            let x = inverse_factorial_f64(y).unwrap();
+            assert!(rel_err(x, n as f64) < 1e-10, "n = {n}: got {x}");
+        }
+        assert!((inverse_factorial_f64(6.0).unwrap() - 3.0).abs() < 1e-10);
+        assert!((inverse_factorial_f64(120.0).unwrap() - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn inverse_factorial_round_trip() {
+        for x in [0.5, 0.75, 1.5, 2.25, 10.1, 99.9] {
+            let y = gamma(x + 1.0);
+            let err = rel_err(inverse_factorial_f64(y).unwrap(), x);
+            assert!(err < 1e-9, "x = {x}: relative error {err}");
+        }
+        let (x_min, y_min) = FACTORIAL_MINIMUM;
+        assert!((inverse_factorial_f64(y_min).unwrap() - x_min).abs() < 1e-6);
+    }
+
+    #[test]
+    fn inverse_factorial_out_of_range() {
+        assert_eq!(inverse_factorial_f64(0.88), None);
+        assert_eq!(inverse_factorial_f64(-1.0), None);
+        assert_eq!(inverse_factorial_f64(f64::NAN), None);
+        assert_eq!(inverse_factorial_f64(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn spouge_out_of_domain() {
+        assert!(factorial_spouge(-1.0, 10).is_nan());
+        assert!(factorial_spouge(-2.5, 10).is_nan());
+    }
+}
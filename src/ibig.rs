@@ -0,0 +1,76 @@
+//! Prime swing for `ibig::UBig`.
+//!
+//! `UBig` doesn't implement the `num-traits` operators the generic
+//! [`Factorial`](crate::Factorial) impl needs, and that impl rules out a
+//! dedicated one, so this backend is exposed as free functions instead. It
+//! shares the batched prime swing of the `BigUint` specialization: small
+//! factors are collected in a machine word and the power of two is applied
+//! with a shift.
+
+use crate::biguint::{odd_factorial, BigNatural};
+use crate::two_exponent;
+use ibig::UBig;
+use primal_sieve::Sieve;
+
+impl BigNatural for UBig {
+    fn square(&self) -> Self {
+        self * self
+    }
+}
+
+/// Returns `n!` as a `UBig` using the prime swing algorithm, if the sieve is
+/// equal or greater than `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::ubig_psw_factorial;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// assert_eq!(ubig_psw_factorial(10, &sieve), Some(3628800u32.into()));
+/// assert_eq!(ubig_psw_factorial(1000, &sieve), None);
+/// ```
+pub fn ubig_psw_factorial(n: usize, sieve: &Sieve) -> Option<UBig> {
+    if sieve.upper_bound() < n {
+        return None;
+    }
+    Some(odd_factorial::<UBig>(n, sieve) << two_exponent(n as u64) as usize)
+}
+
+/// Returns `n!` as a `UBig`, the `ibig` counterpart of
+/// [`Factorial::factorial`](crate::Factorial::factorial) on `BigUint`.
+///
+/// # Examples
+/// ```
+/// use factorial::ubig_factorial;
+/// assert_eq!(ubig_factorial(20), 2432902008176640000u64.into());
+/// ```
+pub fn ubig_factorial(n: usize) -> UBig {
+    ubig_psw_factorial(n, &Sieve::new(n.max(2))).expect("the sieve covers n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn matches_biguint() {
+        let sieve = Sieve::new(3000);
+        for n in (0..300usize).chain([1000, 1234, 2999, 3000]) {
+            assert_eq!(
+                ubig_psw_factorial(n, &sieve).unwrap().to_string(),
+                BigUint::from(n).factorial().to_string(),
+                "mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn sieve_too_small() {
+        let sieve = Sieve::new(100);
+        assert_eq!(ubig_psw_factorial(5000, &sieve), None);
+        assert_eq!(ubig_factorial(0), UBig::from(1u8));
+        assert_eq!(ubig_factorial(1), UBig::from(1u8));
+    }
+}
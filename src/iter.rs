@@ -0,0 +1,196 @@
+//! Iterating over the factorials and the rows of related tables.
+
+use num_traits::{CheckedMul, FromPrimitive, One, Unsigned};
+
+/// An iterator over `0!, 1!, 2!, ...`, created by [`factorials`].
+///
+/// Every factorial is the previous one times the next argument, so the
+/// first `n` factorials take `n` multiplications. For fixed-width types the
+/// iterator ends before the first factorial that overflows, for `BigUint`
+/// it never ends.
+#[derive(Clone, Debug)]
+pub struct Factorials<T> {
+    next: Option<T>,
+    n: usize,
+}
+
+/// Returns an iterator over `0!, 1!, 2!, ...` as long as they fit the type
+/// `T`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorials;
+/// let first: Vec<u32> = factorials().take(6).collect();
+/// assert_eq!(first, [1, 1, 2, 6, 24, 120]);
+/// // 12! is the largest factorial that fits in a u32.
+/// assert_eq!(factorials::<u32>().count(), 13);
+/// ```
+pub fn factorials<T: CheckedMul + One + FromPrimitive>() -> Factorials<T> {
+    Factorials {
+        next: Some(T::one()),
+        n: 0,
+    }
+}
+
+impl<T: CheckedMul + One + FromPrimitive> Iterator for Factorials<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next.take()?;
+        self.n += 1;
+        self.next = T::from_usize(self.n).and_then(|n| current.checked_mul(&n));
+        Some(current)
+    }
+}
+
+/// An iterator over the binomial coefficients `C(n, 0), C(n, 1), ...,
+/// C(n, n)`, created by [`combinations_row`].
+#[derive(Clone, Debug)]
+pub struct CombinationsRow<T> {
+    n: T,
+    k: T,
+    next: Option<T>,
+}
+
+/// Returns an iterator over the row `C(n, 0), C(n, 1), ..., C(n, n)` of
+/// Pascal's triangle.
+///
+/// Every coefficient follows from the previous one by
+/// `C(n, k + 1) = C(n, k) (n - k) / (k + 1)`, a single multiplication and
+/// division. For fixed-width types the row ends early if that product
+/// overflows.
+///
+/// # Examples
+/// ```
+/// use factorial::combinations_row;
+/// let row: Vec<u32> = combinations_row(4).collect();
+/// assert_eq!(row, [1, 4, 6, 4, 1]);
+/// ```
+pub fn combinations_row<T: PartialOrd + Unsigned + CheckedMul + Clone>(n: T) -> CombinationsRow<T> {
+    CombinationsRow {
+        n,
+        k: T::zero(),
+        next: Some(T::one()),
+    }
+}
+
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> Iterator for CombinationsRow<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next.take()?;
+        if self.k < self.n {
+            let remaining = self.n.clone() - self.k.clone();
+            self.k = self.k.clone() + T::one();
+            self.next = current
+                .checked_mul(&remaining)
+                .map(|product| product / self.k.clone());
+        }
+        Some(current)
+    }
+}
+
+/// An iterator over the numbers of permutations `P(n, 0), P(n, 1), ...,
+/// P(n, n)`, created by [`permutations_row`].
+#[derive(Clone, Debug)]
+pub struct PermutationsRow<T> {
+    n: T,
+    k: T,
+    next: Option<T>,
+}
+
+/// Returns an iterator over `P(n, k) = n! / (n - k)!` for `k = 0, 1, ..., n`,
+/// the falling factorials of `n`.
+///
+/// Every term is the previous one times `n - k`. For fixed-width types the
+/// sequence ends before the first term that overflows.
+///
+/// # Examples
+/// ```
+/// use factorial::permutations_row;
+/// let row: Vec<u32> = permutations_row(4).collect();
+/// assert_eq!(row, [1, 4, 12, 24, 24]);
+/// ```
+pub fn permutations_row<T: PartialOrd + Unsigned + CheckedMul + Clone>(n: T) -> PermutationsRow<T> {
+    PermutationsRow {
+        n,
+        k: T::zero(),
+        next: Some(T::one()),
+    }
+}
+
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> Iterator for PermutationsRow<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next.take()?;
+        if self.k < self.n {
+            let factor = self.n.clone() - self.k.clone();
+            self.k = self.k.clone() + T::one();
+            self.next = current.checked_mul(&factor);
+        }
+        Some(current)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn ends_where_checked_factorial_overflows() {
+        let values: Vec<u32> = factorials().take_while(|_| true).collect();
+        assert_eq!(values.len(), 13);
+        for (n, value) in values.iter().enumerate() {
+            assert_eq!(Some(*value), (n as u32).checked_factorial());
+        }
+        assert_eq!((values.len() as u32).checked_factorial(), None);
+        assert_eq!(factorials::<u8>().count(), 6);
+        assert_eq!(factorials::<u64>().count(), 21);
+        assert_eq!(factorials::<u128>().count(), 35);
+    }
+
+    #[test]
+    fn combinations_row_sums_to_power_of_two() {
+        let row: Vec<u32> = combinations_row(6).collect();
+        assert_eq!(row, [1, 6, 15, 20, 15, 6, 1]);
+        assert_eq!(row.iter().sum::<u32>(), 1 << 6);
+        assert_eq!(combinations_row(0u32).collect::<Vec<_>>(), [1]);
+        for n in 0..=60u64 {
+            let row: Vec<u64> = combinations_row(n).collect();
+            assert_eq!(row.len() as u64, n + 1);
+            for (k, c) in row.into_iter().enumerate() {
+                assert_eq!(Some(c), crate::Binomial::checked_binomial(&n, &(k as u64)));
+            }
+        }
+        // C(70, k) (70 - k) overflows a u64 in the middle of the row.
+        assert!(combinations_row(70u64).count() < 71);
+        assert_eq!(combinations_row(BigUint::from(70u32)).count(), 71);
+    }
+
+    #[test]
+    fn permutations_row_matches_falling_factorial() {
+        use crate::FallingFactorial;
+        for n in 0..=30u32 {
+            let n = BigUint::from(n);
+            let row: Vec<BigUint> = permutations_row(n.clone()).collect();
+            for (k, p) in row.iter().enumerate() {
+                assert_eq!(*p, n.falling_factorial(&BigUint::from(k)));
+            }
+            assert_eq!(row.last(), Some(&n.factorial()));
+        }
+        // 13 * 12 * ... * 2 overflows a u32.
+        let row: Vec<u32> = permutations_row(13).collect();
+        assert_eq!(row.len(), 12);
+        assert_eq!(row[11], 13u32.falling_factorial(&11));
+    }
+
+    #[test]
+    fn big_factorials_continue() {
+        for (n, value) in factorials::<BigUint>().take(300).enumerate() {
+            assert_eq!(value, BigUint::from(n).factorial());
+        }
+    }
+}
@@ -1,8 +1,14 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+use core::ops::Shl;
+#[cfg(feature = "std")]
+use num_bigint::{BigInt, Sign};
+#[cfg(feature = "std")]
+use num_traits::CheckedAdd;
 use num_traits::{CheckedMul, FromPrimitive, ToPrimitive, Unsigned};
+#[cfg(feature = "std")]
 use primal_sieve::Sieve;
-use std::ops::Shl;
 
 /// Unary operator for computing the factorial of a number
 ///
@@ -11,6 +17,12 @@ pub trait Factorial<Target = Self> {
     /// Returns `self!`, i.e. the factorial of `self`,
     /// if it doesn't overflow the type `T`.
     ///
+    /// Arguments beyond the built-in tables use the prime swing algorithm.
+    /// With the default `thread-local-sieve` feature its sieve is kept per
+    /// thread and only grown when a larger argument comes along, which trades
+    /// the memory of the largest sieve so far for not sieving on every call.
+    /// Without the feature every call builds and drops its own sieve.
+    ///
     /// # Examples
     /// ```
     /// use factorial::Factorial;
@@ -30,27 +42,180 @@ pub trait Factorial<Target = Self> {
             .expect("Overflow computing factorial")
     }
 
+    /// Returns `self!`, or [`FactorialError::Overflow`] if it overflows the
+    /// type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::{Factorial, FactorialError};
+    /// assert_eq!(10u32.try_factorial(), Ok(3628800));
+    /// assert_eq!(13u32.try_factorial(), Err(FactorialError::Overflow));
+    /// ```
+    fn try_factorial(&self) -> Result<Target, FactorialError> {
+        self.checked_factorial().ok_or(FactorialError::Overflow)
+    }
+
     /// Returns `self!`, i.e. the factorial of `self` using the prime swing algorithm.
     ///
+    /// The sieve must be equal or greater than `self`, i.e.
+    /// `sieve.upper_bound() >= self`, otherwise `None` is returned. `None` is
+    /// also returned if `self!` overflows the type `T`.
+    ///
     /// # Examples
     /// ```
     /// use factorial::Factorial;
     /// use primal_sieve::Sieve;
     /// // The sieve must be equal or greater than the argument of the factorial.
     /// let sieve = Sieve::new(10_usize);
-    /// assert_eq!(10_usize.factorial(), 3628800);
+    /// assert_eq!(10_usize.psw_factorial(&sieve).unwrap(), 3628800);
+    /// ```
+    ///
+    /// A sieve that is too small:
+    /// ```
+    /// use factorial::Factorial;
+    /// use num_bigint::BigUint;
+    /// use primal_sieve::Sieve;
+    /// let sieve = Sieve::new(10_usize);
+    /// assert_eq!(BigUint::from(1000_u32).psw_factorial(&sieve), None);
+    /// ```
+    #[cfg(feature = "std")]
+    fn psw_factorial(&self, sieve: &Sieve) -> Option<Target> {
+        self.try_psw_factorial(sieve).ok()
+    }
+
+    /// Returns `self!` using the prime swing algorithm, or why it can't be
+    /// computed: [`FactorialError::SieveTooSmall`] if the sieve doesn't
+    /// cover `self`, [`FactorialError::Overflow`] if `self!` overflows the
+    /// type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::{Factorial, FactorialError};
+    /// use primal_sieve::Sieve;
+    /// let sieve = Sieve::new(10_usize);
+    /// assert_eq!(10u32.try_psw_factorial(&sieve), Ok(3628800));
+    /// assert!(matches!(
+    ///     1_000_000u32.try_psw_factorial(&sieve),
+    ///     Err(FactorialError::SieveTooSmall { needed: 1_000_000, .. })
+    /// ));
+    /// ```
+    #[cfg(feature = "std")]
+    fn try_psw_factorial(&self, sieve: &Sieve) -> Result<Target, FactorialError>;
+
+    /// Returns `self!` using the prime swing algorithm, or `None` if
+    /// `sieve_upper`, the size the sieve was created with, is smaller than
+    /// `self`.
+    ///
+    /// Like [`Factorial::psw_factorial`] this also checks the bound the
+    /// sieve actually covers.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Factorial;
+    /// use primal_sieve::Sieve;
+    /// let sieve = Sieve::new(100);
+    /// assert_eq!(10_usize.psw_factorial_checked(&sieve, 100), Some(3628800));
+    /// assert_eq!(1000_u128.psw_factorial_checked(&sieve, 100), None);
+    /// ```
+    #[cfg(feature = "std")]
+    fn psw_factorial_checked(&self, sieve: &Sieve, sieve_upper: usize) -> Option<Target>;
+
+    /// Returns `self!` using the prime swing algorithm, if it doesn't
+    /// overflow the type `T`, together with the sieve for the next call.
+    ///
+    /// The given sieve is reused if it covers `self` and otherwise replaced
+    /// by one at least twice as large, the same way
+    /// [`FactorialContext`] grows. Without a sieve a new one is built.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Factorial;
+    /// use num_bigint::BigUint;
+    /// let (res, sieve) = BigUint::from(100u32).factorial_with_sieve(None);
+    /// assert_eq!(res, Some(BigUint::from(100u32).factorial()));
+    /// // The sieve from the last call is handed on to the next one.
+    /// let (res, sieve) = BigUint::from(150u32).factorial_with_sieve(Some(sieve));
+    /// assert_eq!(res, Some(BigUint::from(150u32).factorial()));
+    /// assert!(sieve.upper_bound() >= 150);
+    /// ```
+    #[cfg(feature = "std")]
+    fn factorial_with_sieve(&self, sieve: Option<Sieve>) -> (Option<Target>, Sieve);
+
+    /// Returns `(k, k!)` for the largest `k <= self` such that `k!` fits the
+    /// type `T`.
+    ///
+    /// For types that never overflow this is simply `(self, self!)`.
+    ///
+    /// # Panics
+    /// Panics if `self` doesn't fit in a `usize` and `self!` doesn't overflow
+    /// the type `T` within the built-in table, as the answer would then not
+    /// fit the return type either. Fixed-width types up to `u128` always
+    /// overflow in time.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Factorial;
+    /// assert_eq!(100u32.factorial_partial_until_overflow(), (12, 479001600));
+    /// assert_eq!(5u32.factorial_partial_until_overflow(), (5, 120));
+    /// ```
+    fn factorial_partial_until_overflow(&self) -> (usize, Target);
+
+    /// Returns the exponent of two in `self!`, i.e. `self - popcount(self)`.
+    ///
+    /// This is the special case `p = 2` of Legendre's formula.
+    ///
+    /// # Panics
+    /// Panics if `self` does not fit in a `u64`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Factorial;
+    /// // 10! = 2^8 * 14175
+    /// assert_eq!(10u32.factorial_two_exponent(), 8);
     /// ```
-    fn psw_factorial(&self, sieve: &Sieve) -> Option<Target>;
+    fn factorial_two_exponent(&self) -> u64;
 }
 
 trait PrivateFactorial<Target = Self> {
+    #[cfg(feature = "std")]
+    fn psw_factorial_covered(&self, sieve: &Sieve) -> Option<Target>;
+
+    #[cfg(feature = "std")]
     fn prime_swing(&self, sieve: &Sieve) -> Option<Target>;
 
+    #[cfg(feature = "std")]
     fn odd_factorial(&self, sieve: &Sieve) -> Option<Target>;
 
     fn odd_factorial_array(&self) -> Option<Target>;
 
     fn psw_factorial_with_array(&self) -> Option<Target>;
+
+    fn naive_factorial(&self) -> Option<Target>;
+}
+
+/// Operator for computing the multifactorial `n (n - k) (n - 2k) ...` of a
+/// number, down to the smallest positive term
+///
+/// Implements checked and unchecked versions of the formula
+pub trait Multifactorial<Target = Self> {
+    /// Returns the `k`-multifactorial of `self`, if it doesn't overflow the
+    /// type `T`, or `None` for the meaningless step `k == 0`.
+    ///
+    /// For `k == 1` this is the factorial, for `k == 2` the double factorial.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Multifactorial;
+    /// // 10 * 7 * 4 * 1
+    /// assert_eq!(10u32.checked_multifactorial(3), Some(280));
+    /// assert_eq!(10u32.checked_multifactorial(0), None);
+    /// ```
+    fn checked_multifactorial(&self, k: usize) -> Option<Target>;
+
+    fn multifactorial(&self, k: usize) -> Target {
+        self.checked_multifactorial(k)
+            .expect("Overflow computing multifactorial")
+    }
 }
 
 /// Unary operator for computing the double factorial of a number
@@ -59,242 +224,1825 @@ trait PrivateFactorial<Target = Self> {
 pub trait DoubleFactorial<Target = Self> {
     fn checked_double_factorial(&self) -> Option<Target>;
 
+    /// Returns `self!!` using the prime swing factorials
+    /// `(2k)!! = 2^k k!` and `(2k + 1)!! = (2k + 1)! / (2^k k!)`, if it
+    /// doesn't overflow the type `T`.
+    ///
+    /// The sieve must be equal or greater than `self`, otherwise `None` is
+    /// returned. Small arguments are multiplied out directly.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::DoubleFactorial;
+    /// use num_bigint::BigUint;
+    /// use primal_sieve::Sieve;
+    /// let sieve = Sieve::new(100);
+    /// let n = BigUint::from(100u32);
+    /// assert_eq!(n.psw_double_factorial(&sieve), Some(n.double_factorial()));
+    /// ```
+    #[cfg(feature = "std")]
+    fn psw_double_factorial(&self, sieve: &Sieve) -> Option<Target>;
+
     fn double_factorial(&self) -> Target {
         self.checked_double_factorial()
             .expect("Overflow computing double factorial")
     }
 }
 
-mod array;
+/// Unary operator for computing the falling factorial
+/// `n (n - 1) ... (n - k + 1) = n! / (n - k)!`, the number of ordered
+/// selections of `k` out of `n` items
+///
+/// Implements checked and unchecked versions of the formula
+pub trait FallingFactorial<Target = Self> {
+    /// Returns `self! / (self - k)!` if it doesn't overflow the type `T`.
+    ///
+    /// Only the `k` descending terms are multiplied, so this works for
+    /// large `self` as long as the result fits. It is `1` for `k == 0` and
+    /// `0` for `k > self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::FallingFactorial;
+    /// assert_eq!(10u32.checked_falling_factorial(&3), Some(720));
+    /// assert_eq!(1259u64.checked_falling_factorial(&4), Some(2_500_525_503_024));
+    /// assert_eq!(3u32.checked_falling_factorial(&4), Some(0));
+    /// ```
+    fn checked_falling_factorial(&self, k: &Self) -> Option<Target>;
 
-fn prime_range(
-    sieve: &Sieve,
-    lower_bound: usize,
-    upper_boud: usize,
-) -> impl Iterator<Item = usize> + '_ {
-    sieve
-        .primes_from(lower_bound)
-        .take_while(move |m| *m <= upper_boud)
+    fn falling_factorial(&self, k: &Self) -> Target {
+        self.checked_falling_factorial(k)
+            .expect("Overflow computing falling factorial")
+    }
 }
 
-impl<
-        T: PartialOrd
-            + Unsigned
-            + CheckedMul
-            + Clone
-            + FromPrimitive
-            + ToPrimitive
-            + Shl<u32, Output = T>,
-    > Factorial<T> for T
-{
-    #[inline(always)]
-    fn checked_factorial(&self) -> Option<T> {
-        if self < &T::from_usize(array::SMALL_ODD_SWING.len()).unwrap() {
-            return self.psw_factorial_with_array();
+/// Binary operator for computing the binomial coefficient
+/// `C(n, k) = n! / (k! (n - k)!)`
+///
+/// Implements checked and unchecked versions of the formula
+#[cfg(feature = "std")]
+pub trait Binomial<Target = Self> {
+    /// Returns `C(self, k)` if it doesn't overflow the type `T`.
+    ///
+    /// The multiplicative formula keeps the running value an integer, so this
+    /// overflows much later than the factorials would: `C(60, 30)` fits in a
+    /// `u128` while `60!` does not. The coefficient is `0` for `k > self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Binomial;
+    /// assert_eq!(5u32.checked_binomial(&2), Some(10));
+    /// assert_eq!(60u128.checked_binomial(&30), Some(118_264_581_564_861_424));
+    /// assert_eq!(2u32.checked_binomial(&3), Some(0));
+    /// ```
+    fn checked_binomial(&self, k: &Self) -> Option<Target>;
+
+    fn binomial(&self, k: &Self) -> Target {
+        self.checked_binomial(k)
+            .expect("Overflow computing binomial coefficient")
+    }
+}
+
+/// Unary operator for computing the subfactorial `!n`, the number of
+/// derangements (permutations without a fixed point) of `n` elements
+///
+/// Implements checked and unchecked versions of the formula
+#[cfg(feature = "std")]
+pub trait Subfactorial<Target = Self> {
+    /// Returns `!self` if it doesn't overflow the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Subfactorial;
+    /// assert_eq!(4u32.checked_subfactorial(), Some(9));
+    /// assert_eq!(20u32.checked_subfactorial(), None);
+    /// ```
+    fn checked_subfactorial(&self) -> Option<Target>;
+
+    fn subfactorial(&self) -> Target {
+        self.checked_subfactorial()
+            .expect("Overflow computing subfactorial")
+    }
+}
+
+/// Unary operator for computing the primorial `n#`, the product of all
+/// primes up to `n`
+///
+/// Implements checked and unchecked versions of the formula
+#[cfg(feature = "std")]
+pub trait Primorial<Target = Self> {
+    /// Returns `self#` if it doesn't overflow the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Primorial;
+    /// // 2 * 3 * 5 * 7
+    /// assert_eq!(10u32.checked_primorial(), Some(210));
+    /// assert_eq!(1u32.checked_primorial(), Some(1));
+    /// ```
+    fn checked_primorial(&self) -> Option<Target>;
+
+    fn primorial(&self) -> Target {
+        self.checked_primorial()
+            .expect("Overflow computing primorial")
+    }
+
+    /// Returns `self#` using an existing sieve, if it doesn't overflow the
+    /// type `T`.
+    ///
+    /// Returns `None` if the sieve doesn't reach `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Primorial;
+    /// use primal_sieve::Sieve;
+    /// let sieve = Sieve::new(100);
+    /// assert_eq!(30u64.psw_primorial(&sieve), Some(6469693230));
+    /// ```
+    fn psw_primorial(&self, sieve: &Sieve) -> Option<Target>;
+}
+
+/// Unary operator for computing the hyperfactorial
+/// `H(n) = 1^1 * 2^2 * ... * n^n`
+///
+/// Implements checked and unchecked versions of the formula
+pub trait Hyperfactorial<Target = Self> {
+    /// Returns `H(self)` if it doesn't overflow the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Hyperfactorial;
+    /// assert_eq!(4u32.checked_hyperfactorial(), Some(27648));
+    /// assert_eq!(6u32.checked_hyperfactorial(), None);
+    /// ```
+    fn checked_hyperfactorial(&self) -> Option<Target>;
+
+    fn hyperfactorial(&self) -> Target {
+        self.checked_hyperfactorial()
+            .expect("Overflow computing hyperfactorial")
+    }
+}
+
+/// Unary operator for computing the superfactorial
+/// `sf(n) = 1! * 2! * ... * n!`
+///
+/// Implements checked and unchecked versions of the formula
+pub trait Superfactorial<Target = Self> {
+    /// Returns `sf(self)` if it doesn't overflow the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Superfactorial;
+    /// assert_eq!(4u32.checked_superfactorial(), Some(288));
+    /// assert_eq!(7u32.checked_superfactorial(), None);
+    /// ```
+    fn checked_superfactorial(&self) -> Option<Target>;
+
+    fn superfactorial(&self) -> Target {
+        self.checked_superfactorial()
+            .expect("Overflow computing superfactorial")
+    }
+}
+
+/// Computes the natural logarithm of the factorial as an `f64`, without
+/// computing the factorial itself
+#[cfg(feature = "std")]
+pub trait LogFactorial {
+    /// Returns `ln(self!)`.
+    ///
+    /// Small arguments are looked up exactly, larger ones use Stirling's
+    /// series `n ln n - n + ln(2πn) / 2 + 1 / (12n) - ...`, which is accurate
+    /// to the precision of an `f64` there.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::LogFactorial;
+    /// assert!((10u32.log_factorial() - 3628800f64.ln()).abs() < 1e-12);
+    /// // ln(1000000!) without computing the 5.5 million digit number
+    /// assert!((1_000_000u64.log_factorial() - 12_815_518.384_658_17).abs() < 1e-6);
+    /// ```
+    fn log_factorial(&self) -> f64;
+
+    /// Returns the logarithm of `self!` to the given base.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::LogFactorial;
+    /// // 10! = 3628800 has seven digits
+    /// assert_eq!(10u32.log_factorial_base(10.0).floor(), 6.0);
+    /// ```
+    fn log_factorial_base(&self, base: f64) -> f64 {
+        self.log_factorial() / base.ln()
+    }
+}
+
+/// Computes a fast floating point estimate of the factorial
+#[cfg(feature = "std")]
+pub trait ApproxFactorial {
+    /// Returns `self!` approximated by Stirling's formula with three
+    /// correction terms, or infinity if `self!` exceeds `f64::MAX`, which is
+    /// the case from `171!` on.
+    ///
+    /// The relative error is about `2.3e-4 / n^4`: below `3e-4` for `n = 1`,
+    /// `3e-8` for `n = 10`, and at the precision of an `f64` from a few
+    /// hundred on.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::ApproxFactorial;
+    /// assert!((10u32.approx_factorial() / 3628800.0 - 1.0).abs() < 1e-7);
+    /// assert_eq!(171u32.approx_factorial(), f64::INFINITY);
+    /// ```
+    fn approx_factorial(&self) -> f64;
+}
+
+/// Binary operator for computing the factorial modulo some number
+///
+/// Implements checked and unchecked versions of the formula
+#[cfg(feature = "std")]
+pub trait FactorialMod<Target = Self> {
+    /// Returns `self! mod modulus`, or `None` if `modulus` is zero.
+    ///
+    /// The product is reduced after every multiplication, so it never
+    /// overflows and the factorial itself is never computed. For
+    /// `self >= modulus` the result is `0` straight away.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::FactorialMod;
+    /// assert_eq!(10u32.checked_factorial_mod(&1000), Some(800));
+    /// assert_eq!(10u32.checked_factorial_mod(&0), None);
+    /// ```
+    fn checked_factorial_mod(&self, modulus: &Self) -> Option<Target>;
+
+    fn factorial_mod(&self, modulus: &Self) -> Target {
+        self.checked_factorial_mod(modulus)
+            .expect("Zero modulus computing factorial mod")
+    }
+}
+
+/// Binary operator for computing the exponent of a prime in the factorial
+pub trait PrimeExponent {
+    /// Returns the exponent of the prime `p` in `self!`, using Legendre's
+    /// formula `sum_{i >= 1} floor(self / p^i)`.
+    ///
+    /// The exponent is smaller than `self`, so this never overflows, and it
+    /// takes only `log_p(self)` divisions. `p` is not checked to be prime.
+    ///
+    /// # Panics
+    /// Panics if `p < 2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::PrimeExponent;
+    /// assert_eq!(10u32.prime_exponent_in_factorial(&2), 8);
+    /// assert_eq!(25u32.prime_exponent_in_factorial(&5), 6);
+    /// ```
+    fn prime_exponent_in_factorial(&self, p: &Self) -> Self;
+}
+
+/// Unary operator for counting the trailing zeros of the factorial
+pub trait TrailingZeros {
+    /// Returns the number of trailing zeros of `self!` in base ten, the
+    /// exponent of five `sum_{i >= 1} floor(self / 5^i)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::TrailingZeros;
+    /// assert_eq!(100u32.trailing_zeros_of_factorial(), 24);
+    /// ```
+    fn trailing_zeros_of_factorial(&self) -> Self;
+
+    /// Returns the number of trailing zeros of `self!` in the given base,
+    /// the minimum over the prime powers `p^e` of `base` of the exponent of
+    /// `p` in `self!` divided by `e`.
+    ///
+    /// The base is factored by trial division.
+    ///
+    /// # Panics
+    /// Panics if `base < 2`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::TrailingZeros;
+    /// // 10! = 0x375f00
+    /// assert_eq!(10u32.trailing_zeros_of_factorial_base(&16), 2);
+    /// assert_eq!(10u32.trailing_zeros_of_factorial_base(&7), 1);
+    /// ```
+    fn trailing_zeros_of_factorial_base(&self, base: &Self) -> Self;
+}
+
+/// Arguments below this are answered from the precomputed tables: the odd
+/// part of the swing directly, the factorial by
+/// `PrivateFactorial::psw_factorial_with_array`.
+const SMALL_SWING_LEN: usize = array::SMALL_ODD_SWING.len();
+
+/// Unary operator for computing the gamma function and the factorial of real
+/// numbers, `x! = Γ(x + 1)`
+#[cfg(feature = "std")]
+pub trait Gamma {
+    /// Returns `Γ(self)`, see [`gamma`](crate::gamma()) for the methods
+    /// used.
+    ///
+    /// The result is accurate to about `1e-14` relative for typical
+    /// arguments. Non-positive integers are poles, for which `NaN` is
+    /// returned.
+    ///
+    /// The standard library has an unstable `f64::gamma`, so calling this as
+    /// `Gamma::gamma(&x)` avoids the `unstable_name_collisions` lint.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Gamma;
+    /// assert!((Gamma::gamma(&5.0) - 24.0).abs() < 1e-12);
+    /// assert!((Gamma::gamma(&0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-14);
+    /// ```
+    fn gamma(&self) -> f64;
+
+    /// Returns `self! = Γ(self + 1)`, interpolating the factorial between the
+    /// integers.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::Gamma;
+    /// assert!((4.0.factorial() - 24.0).abs() < 1e-12);
+    /// // (1/2)! = √π / 2
+    /// assert!((0.5.factorial() - std::f64::consts::PI.sqrt() / 2.0).abs() < 1e-14);
+    /// ```
+    fn factorial(&self) -> f64;
+}
+
+/// Unary operator for computing the factorial of a signed number, which is
+/// undefined for negative numbers
+///
+/// Implements checked and unchecked versions of the formula
+pub trait SignedFactorial<Target = Self> {
+    /// Returns `self!` if `self` isn't negative and the factorial doesn't
+    /// overflow the type `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::SignedFactorial;
+    /// assert_eq!(5i32.checked_factorial(), Some(120));
+    /// assert_eq!((-3i32).checked_factorial(), None);
+    /// assert_eq!(13i32.checked_factorial(), None);
+    /// ```
+    fn checked_factorial(&self) -> Option<Target> {
+        self.try_factorial().ok()
+    }
+
+    /// Returns `self!`, or [`FactorialError::Negative`] if `self` is negative
+    /// and [`FactorialError::Overflow`] if the factorial overflows the type
+    /// `T`.
+    ///
+    /// # Examples
+    /// ```
+    /// use factorial::{FactorialError, SignedFactorial};
+    /// assert_eq!(5i32.try_factorial(), Ok(120));
+    /// assert_eq!((-3i32).try_factorial(), Err(FactorialError::Negative));
+    /// assert_eq!(13i32.try_factorial(), Err(FactorialError::Overflow));
+    /// ```
+    fn try_factorial(&self) -> Result<Target, FactorialError>;
+
+    /// Returns `self!`.
+    ///
+    /// # Panics
+    /// Panics if `self` is negative or the factorial overflows the type `T`.
+    fn factorial(&self) -> Target;
+}
+
+macro_rules! signed_factorial {
+    ($($signed:ty => $unsigned:ty),*) => {$(
+        impl SignedFactorial for $signed {
+            fn try_factorial(&self) -> Result<$signed, FactorialError> {
+                let n = <$unsigned>::try_from(*self).map_err(|_| FactorialError::Negative)?;
+                Factorial::checked_factorial(&n)
+                    .and_then(|f| <$signed>::try_from(f).ok())
+                    .ok_or(FactorialError::Overflow)
+            }
+
+            fn factorial(&self) -> $signed {
+                assert!(*self >= 0, "factorial of negative number is undefined");
+                SignedFactorial::checked_factorial(self).expect("Overflow computing factorial")
+            }
+        }
+    )*};
+}
+
+signed_factorial!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// `BigInt` can't implement [`Factorial`] next to its blanket impl for
+/// unsigned types, so it implements this trait instead: non-negative values
+/// go through [`BigUint`](num_bigint::BigUint) and never overflow.
+///
+/// # Examples
+/// ```
+/// use factorial::SignedFactorial;
+/// use num_bigint::ToBigInt;
+/// assert_eq!(5.to_bigint().unwrap().factorial(), 120.to_bigint().unwrap());
+/// assert_eq!((-1).to_bigint().unwrap().checked_factorial(), None);
+/// ```
+#[cfg(feature = "std")]
+impl SignedFactorial for BigInt {
+    fn try_factorial(&self) -> Result<BigInt, FactorialError> {
+        let n = self.to_biguint().ok_or(FactorialError::Negative)?;
+        Factorial::try_factorial(&n).map(BigInt::from)
+    }
+
+    fn factorial(&self) -> BigInt {
+        assert!(
+            self.sign() != Sign::Minus,
+            "factorial of negative number is undefined"
+        );
+        SignedFactorial::checked_factorial(self).expect("Overflow computing factorial")
+    }
+}
+
+/// Returns `n!`, the same as [`Factorial::factorial`].
+///
+/// # Panics
+/// Panics if `n!` overflows the type `T`.
+///
+/// # Examples
+/// ```
+/// assert_eq!(factorial::factorial(10u32), 3628800);
+/// ```
+pub fn factorial<T: Factorial>(n: T) -> T {
+    n.factorial()
+}
+
+/// Returns `n!` if it doesn't overflow the type `T`, the same as
+/// [`Factorial::checked_factorial`].
+///
+/// # Examples
+/// ```
+/// assert_eq!(factorial::checked_factorial(10u32), Some(3628800));
+/// assert_eq!(factorial::checked_factorial(13u32), None);
+/// ```
+pub fn checked_factorial<T: Factorial>(n: T) -> Option<T> {
+    n.checked_factorial()
+}
+
+/// Returns `n!!`, the same as [`DoubleFactorial::double_factorial`].
+///
+/// # Panics
+/// Panics if `n!!` overflows the type `T`.
+///
+/// # Examples
+/// ```
+/// assert_eq!(factorial::double_factorial(9u32), 945);
+/// ```
+pub fn double_factorial<T: DoubleFactorial>(n: T) -> T {
+    n.double_factorial()
+}
+
+/// Returns `n!` for `n <= 34`, the factorials that fit in a `u128`, in a
+/// `const` context.
+///
+/// Trait methods can't be `const`, so this is a lookup in the precomputed
+/// table, e.g. to build lookup tables at compile time.
+///
+/// # Panics
+/// Panics if `n > 34`, which is a compile error in a `const` context.
+///
+/// # Examples
+/// ```
+/// use factorial::const_factorial;
+/// const TABLE: [u128; 4] = [
+///     const_factorial(5),
+///     const_factorial(10),
+///     const_factorial(20),
+///     const_factorial(34),
+/// ];
+/// assert_eq!(TABLE[1], 3628800);
+/// ```
+///
+/// ```compile_fail
+/// const TOO_LARGE: u128 = factorial::const_factorial(35);
+/// ```
+pub const fn const_factorial(n: u32) -> u128 {
+    assert!(
+        (n as usize) < array::SMALL_FACTORIAL.len(),
+        "const_factorial: n! overflows u128 for n > 34"
+    );
+    array::SMALL_FACTORIAL[n as usize]
+}
+
+/// Calls `f(p, e)` for every prime power `p^e` exactly dividing `m`, in
+/// increasing order of `p`, factoring `m` by trial division.
+fn for_each_prime_power<T>(m: &T, mut f: impl FnMut(&T, T))
+where
+    T: PartialOrd + Unsigned + Clone,
+{
+    let mut rest = m.clone();
+    let mut p = T::one() + T::one();
+    while rest > T::one() {
+        if p > rest.clone() / p.clone() {
+            // What is left is a prime.
+            p = rest.clone();
+        }
+        let mut e = T::zero();
+        while (rest.clone() % p.clone()).is_zero() {
+            rest = rest / p.clone();
+            e = e + T::one();
+        }
+        if !e.is_zero() {
+            f(&p, e);
+        }
+        p = p + T::one();
+    }
+}
+
+/// Returns the exponent of two in `n!`.
+fn two_exponent(n: u64) -> u64 {
+    n - u64::from(n.count_ones())
+}
+
+mod array;
+#[cfg(feature = "std")]
+mod bhargava;
+#[cfg(feature = "std")]
+mod biguint;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod combinatorics;
+#[cfg(feature = "std")]
+mod deferred;
+mod error;
+#[cfg(feature = "std")]
+mod estimate;
+#[cfg(feature = "std")]
+mod explain;
+#[cfg(feature = "std")]
+mod factoradic;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+#[cfg(feature = "std")]
+mod gamma;
+#[cfg(feature = "ibig")]
+mod ibig;
+mod iter;
+#[cfg(feature = "num-modular")]
+mod modint;
+#[cfg(feature = "std")]
+mod modular;
+#[cfg(feature = "std")]
+mod number_theory;
+#[cfg(feature = "std")]
+mod product;
+#[cfg(feature = "std")]
+mod progress;
+#[cfg(feature = "std")]
+mod ratio;
+#[cfg(feature = "std")]
+mod recreational;
+#[cfg(feature = "std")]
+mod search;
+#[cfg(feature = "std")]
+mod special;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+mod swing;
+#[cfg(feature = "std")]
+mod value;
+#[cfg(feature = "std")]
+mod wrapping;
+
+#[cfg(feature = "std")]
+pub use bhargava::{bhargava_factorial, bhargava_factorial_integers, p_sequence};
+#[cfg(feature = "std")]
+pub use biguint::{factorial_into, factorial_iterative};
+#[cfg(feature = "std")]
+pub use cache::{factorial_cow, factorial_or_known, FactorialContext, IncrementalFactorial};
+#[cfg(feature = "std")]
+pub use combinatorics::{
+    combinations_with_repetition, falling_factorial_ring, motzkin, rencontres,
+};
+#[cfg(feature = "std")]
+pub use deferred::DeferredFactorial;
+pub use error::FactorialError;
+#[cfg(feature = "std")]
+pub use estimate::{
+    factorial_bit_length, factorial_byte_size, factorial_digit_count, factorial_error_vs_stirling,
+    factorial_summary, factorial_with_limit, log_factorial_stieltjes, smallest_n_with_digits,
+    FactorialSummary, SUMMARY_EXACT_DIGITS,
+};
+#[cfg(feature = "std")]
+pub use explain::{factorial_explain, FactorialPath};
+#[cfg(feature = "std")]
+pub use factoradic::{from_factoradic, to_factoradic};
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::FactorialArg;
+#[cfg(feature = "std")]
+pub use gamma::{beta, digamma, factorial_spouge, gamma, gamma_ratio, inverse_factorial_f64};
+#[cfg(feature = "ibig")]
+pub use ibig::{ubig_factorial, ubig_psw_factorial};
+pub use iter::{
+    combinations_row, factorials, permutations_row, CombinationsRow, Factorials, PermutationsRow,
+};
+#[cfg(feature = "num-modular")]
+pub use modint::ModularFactorial;
+#[cfg(feature = "std")]
+pub use modular::{
+    factorial_mod_mersenne, factorial_mod_sequence, factorial_rns, factorial_with_residues,
+    hyperfactorial_mod, rns_to_biguint, superfactorial_mod, wilson_residue, BinomialModTable,
+    MontgomeryFactorial,
+};
+#[cfg(feature = "std")]
+pub use number_theory::{
+    factorial_divisor_count, factorial_divisor_function_values, factorial_gcd,
+    factorial_is_squarefree, factorial_lcm, factorial_strip_factor, factorial_totient,
+    factorial_trailing_zeros_base, factorial_valuation_vector, is_wolstenholme_prime,
+    smallest_factorial_divisible_by,
+};
+#[cfg(feature = "std")]
+pub use product::{
+    arithmetic_factorial, factorial_by_residue, factorial_split, generalized_factorial,
+    log_semiring_factorial,
+};
+#[cfg(feature = "std")]
+pub use progress::FactorialProgress;
+#[cfg(feature = "std")]
+pub use ratio::factorial_ratio_rational;
+#[cfg(feature = "std")]
+pub use recreational::is_factorion;
+#[cfg(feature = "std")]
+pub use search::{count_factorials_below, factorials_equal, factorials_in_range};
+#[cfg(feature = "std")]
+pub use special::{
+    exponential_factorial, exponential_factorial_digit_count, hyperfactorial_digit_count,
+    hyperfactorial_ratio,
+};
+#[cfg(feature = "std")]
+pub use stats::{binomial_pmf, factorial_f32, factorial_f64, gamma_f64, ln_factorial_f64};
+#[cfg(feature = "std")]
+pub use swing::{odd_factorial, swing};
+#[cfg(feature = "std")]
+pub use value::FactorialValue;
+#[cfg(feature = "std")]
+pub use wrapping::wrapping_factorial;
+
+#[cfg(feature = "std")]
+fn prime_range(
+    sieve: &Sieve,
+    lower_bound: usize,
+    upper_boud: usize,
+) -> impl Iterator<Item = usize> + '_ {
+    sieve
+        .primes_from(lower_bound)
+        .take_while(move |m| *m <= upper_boud)
+}
+
+impl<
+        T: PartialOrd
+            + Unsigned
+            + CheckedMul
+            + Clone
+            + FromPrimitive
+            + ToPrimitive
+            + Shl<u32, Output = T>,
+    > Factorial<T> for T
+{
+    #[inline(always)]
+    fn checked_factorial(&self) -> Option<T> {
+        let Some(table_len) = T::from_usize(SMALL_SWING_LEN) else {
+            // Types this small can't index the tables safely, and only hold
+            // a handful of factorials anyway.
+            return self.naive_factorial();
+        };
+        if self < &table_len {
+            return self.psw_factorial_with_array();
+        }
+        #[cfg(feature = "thread-local-sieve")]
+        return cache::with_thread_sieve(self.to_usize()?, |sieve| self.psw_factorial(sieve));
+        #[cfg(all(feature = "std", not(feature = "thread-local-sieve")))]
+        return self.psw_factorial(&Sieve::new(self.to_usize()?));
+        #[cfg(not(feature = "std"))]
+        return None;
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn try_psw_factorial(&self, sieve: &Sieve) -> Result<T, FactorialError> {
+        let needed = self.to_usize().ok_or(FactorialError::Overflow)?;
+        if sieve.upper_bound() < needed {
+            return Err(FactorialError::SieveTooSmall {
+                needed,
+                have: sieve.upper_bound(),
+            });
+        }
+        self.psw_factorial_covered(sieve)
+            .ok_or(FactorialError::Overflow)
+    }
+
+    #[cfg(feature = "std")]
+    fn psw_factorial_checked(&self, sieve: &Sieve, sieve_upper: usize) -> Option<T> {
+        if sieve_upper < self.to_usize()? {
+            return None;
+        }
+        self.psw_factorial(sieve)
+    }
+
+    #[cfg(feature = "std")]
+    fn factorial_with_sieve(&self, sieve: Option<Sieve>) -> (Option<T>, Sieve) {
+        let Some(n) = self.to_usize() else {
+            return (None, sieve.unwrap_or_else(|| Sieve::new(2)));
+        };
+        let sieve = match sieve {
+            Some(sieve) if sieve.upper_bound() >= n => sieve,
+            Some(sieve) => Sieve::new(n.max(2 * sieve.upper_bound())),
+            None => Sieve::new(n.max(2)),
+        };
+        (self.psw_factorial(&sieve), sieve)
+    }
+
+    fn factorial_partial_until_overflow(&self) -> (usize, T) {
+        let (n, fits) = match self.to_usize() {
+            Some(n) => (n, true),
+            // Far enough to find the overflow of any type that can have one
+            // for arguments this large.
+            None => (array::SMALL_FACTORIAL.len() + 1, false),
+        };
+        let mut k = 0;
+        let mut acc = T::one();
+        while k < n {
+            if k == array::SMALL_FACTORIAL.len() {
+                // The type holds everything in the table, so try the fast path
+                // before falling back to multiplying one term at a time.
+                if let Some(res) = self.checked_factorial() {
+                    return (n, res);
+                }
+            }
+            match T::from_usize(k + 1).and_then(|i| acc.checked_mul(&i)) {
+                Some(next) => {
+                    acc = next;
+                    k += 1;
+                }
+                None => break,
+            }
+        }
+        assert!(
+            fits || k < n,
+            "Argument too large to compute the factorial until overflow"
+        );
+        (k, acc)
+    }
+
+    fn factorial_two_exponent(&self) -> u64 {
+        two_exponent(
+            self.to_u64()
+                .expect("Argument too large to compute the exponent of two"),
+        )
+    }
+}
+
+impl<
+        T: PartialOrd
+            + Unsigned
+            + CheckedMul
+            + Clone
+            + FromPrimitive
+            + ToPrimitive
+            + Shl<u32, Output = T>,
+    > PrivateFactorial<T> for T
+{
+    #[cfg(feature = "std")]
+    fn psw_factorial_covered(&self, sieve: &Sieve) -> Option<T> {
+        let Some(table_len) = T::from_usize(SMALL_SWING_LEN) else {
+            return self.naive_factorial();
+        };
+        if self < &table_len {
+            return self.psw_factorial_with_array();
+        }
+        let bytes = two_exponent(u64::from(self.to_u32()?)) as u32 - 1;
+        let res = self.odd_factorial(sieve)?;
+        res.checked_mul(&T::from_u8(2)?.shl(bytes))
+    }
+
+    #[cfg(feature = "std")]
+    fn prime_swing(&self, sieve: &Sieve) -> Option<T> {
+        let n = self.to_usize()?;
+        if n < SMALL_SWING_LEN {
+            return T::from_u128(array::SMALL_ODD_SWING[n]);
+        }
+        // Collect factors in a machine word first, so a big T allocates for
+        // every batch instead of every prime. A batch that doesn't fit T
+        // divides the swing, so the swing doesn't fit either.
+        let mut product = Some(T::one());
+        let mut batch = 1u64;
+        biguint::for_each_swing_factor(n, sieve, |factor| {
+            let factor = factor as u64;
+            match batch.checked_mul(factor) {
+                Some(next) => batch = next,
+                None => {
+                    product = product
+                        .take()
+                        .zip(T::from_u64(batch))
+                        .and_then(|(product, batch)| product.checked_mul(&batch));
+                    batch = factor;
+                }
+            }
+        });
+        product?.checked_mul(&T::from_u64(batch)?)
+    }
+
+    #[cfg(feature = "std")]
+    fn odd_factorial(&self, sieve: &Sieve) -> Option<T> {
+        let two = T::from_u8(2).unwrap();
+        if self < &(two) {
+            return Some(Self::one());
+        }
+        let tmp = (self.clone() / two).odd_factorial(sieve)?;
+        let tmp_sq = tmp.checked_mul(&tmp)?;
+        tmp_sq.checked_mul(&self.prime_swing(sieve)?)
+    }
+
+    fn odd_factorial_array(&self) -> Option<T> {
+        let two = T::from_u8(2).unwrap();
+        if self < &(two) {
+            return Some(Self::one());
+        }
+        let tmp = (self.clone() / two).odd_factorial_array()?;
+        let tmp_sq = tmp.checked_mul(&tmp)?;
+        tmp_sq.checked_mul(&T::from_u128(array::SMALL_ODD_SWING[self.to_usize()?])?)
+    }
+
+    fn psw_factorial_with_array(&self) -> Option<T> {
+        if self < &T::from_usize(array::SMALL_FACTORIAL.len()).unwrap() {
+            return T::from_u128(array::SMALL_FACTORIAL[self.to_usize().unwrap()]);
+        }
+        let bytes = two_exponent(u64::from(self.to_u32()?)) as u32 - 1;
+        let res = self.odd_factorial_array()?;
+        res.checked_mul(&T::from_u8(2)?.shl(bytes))
+    }
+
+    fn naive_factorial(&self) -> Option<T> {
+        let mut acc = T::one();
+        let mut i = T::one();
+        while &i < self {
+            i = i + T::one();
+            acc = acc.checked_mul(&i)?;
+        }
+        Some(acc)
+    }
+}
+
+/// Arguments from which on the double factorial is derived from the
+/// factorial. Below it, a loop is faster, and the double factorials of all
+/// primitive types overflow before reaching it.
+const DOUBLE_FACTORIAL_LOOP_LIMIT: usize = 64;
+
+/// Returns whether `n` is at least [`DOUBLE_FACTORIAL_LOOP_LIMIT`].
+fn above_loop_limit<T: PartialOrd + FromPrimitive>(n: &T) -> bool {
+    T::from_usize(DOUBLE_FACTORIAL_LOOP_LIMIT).is_some_and(|limit| n >= &limit)
+}
+
+/// Returns `n!!` from `(2k)!! = 2^k k!` and `(2k + 1)!! = (2k + 1)! / (2^k k!)`,
+/// with the factorials computed by `factorial`.
+fn double_factorial_from<T, F>(n: &T, factorial: F) -> Option<T>
+where
+    T: Unsigned + CheckedMul + Clone + ToPrimitive + Shl<u32, Output = T>,
+    F: Fn(&T) -> Option<T>,
+{
+    let two = T::one() + T::one();
+    let half = n.clone() / two.clone();
+    let even = factorial(&half)?.checked_mul(&T::one().shl(half.to_u32()?))?;
+    if (n.clone() % two).is_zero() {
+        Some(even)
+    } else {
+        Some(factorial(n)? / even)
+    }
+}
+
+/// Returns `n (n - k) (n - 2k) ...` by multiplying one term at a time.
+fn multifactorial_loop<T: PartialOrd + Unsigned + CheckedMul + Clone + FromPrimitive>(
+    n: &T,
+    k: usize,
+) -> Option<T> {
+    let Some(step) = T::from_usize(k) else {
+        // The step exceeds the type, so n is the only term.
+        return Some(if n.is_zero() { T::one() } else { n.clone() });
+    };
+    let mut acc = T::one();
+    let mut term = n.clone();
+    while !term.is_zero() {
+        acc = acc.checked_mul(&term)?;
+        if term < step {
+            break;
+        }
+        term = term - step.clone();
+    }
+    Some(acc)
+}
+
+impl<
+        T: PartialOrd
+            + Unsigned
+            + CheckedMul
+            + Clone
+            + FromPrimitive
+            + ToPrimitive
+            + Shl<u32, Output = T>,
+    > Multifactorial<T> for T
+{
+    fn checked_multifactorial(&self, k: usize) -> Option<T> {
+        match k {
+            0 => None,
+            1 => self.checked_factorial(),
+            2 => {
+                // Without the prime swing the factorial stops at the tables.
+                if !cfg!(feature = "std") || !above_loop_limit(self) {
+                    return multifactorial_loop(self, 2);
+                }
+                double_factorial_from(self, T::checked_factorial)
+            }
+            _ => multifactorial_loop(self, k),
+        }
+    }
+}
+
+impl<
+        T: PartialOrd
+            + Unsigned
+            + CheckedMul
+            + Clone
+            + FromPrimitive
+            + ToPrimitive
+            + Shl<u32, Output = T>,
+    > DoubleFactorial<T> for T
+{
+    #[inline(always)]
+    fn checked_double_factorial(&self) -> Option<T> {
+        self.checked_multifactorial(2)
+    }
+
+    #[cfg(feature = "std")]
+    fn psw_double_factorial(&self, sieve: &Sieve) -> Option<T> {
+        if sieve.upper_bound() < self.to_usize()? {
+            return None;
+        }
+        if !above_loop_limit(self) {
+            return multifactorial_loop(self, 2);
+        }
+        double_factorial_from(self, |n| n.psw_factorial(sieve))
+    }
+}
+
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> FallingFactorial<T> for T {
+    fn checked_falling_factorial(&self, k: &T) -> Option<T> {
+        if k > self {
+            return Some(T::zero());
+        }
+        let mut acc = T::one();
+        let mut term = self.clone();
+        let mut i = T::zero();
+        while &i < k {
+            acc = acc.checked_mul(&term)?;
+            term = term - T::one();
+            i = i + T::one();
+        }
+        Some(acc)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> Binomial<T> for T {
+    fn checked_binomial(&self, k: &T) -> Option<T> {
+        combinatorics::checked_binomial(self, k)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + Unsigned + CheckedMul + CheckedAdd + Clone> Subfactorial<T> for T {
+    fn checked_subfactorial(&self) -> Option<T> {
+        special::checked_subfactorial(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Unsigned + CheckedMul + FromPrimitive + ToPrimitive> Primorial<T> for T {
+    fn checked_primorial(&self) -> Option<T> {
+        let n = self.to_usize()?;
+        if n < 2 {
+            return Some(T::one());
+        }
+        self.psw_primorial(&Sieve::new(n))
+    }
+
+    fn psw_primorial(&self, sieve: &Sieve) -> Option<T> {
+        let n = self.to_usize()?;
+        if n < 2 {
+            return Some(T::one());
+        }
+        if sieve.upper_bound() < n {
+            return None;
+        }
+        let mut acc = T::one();
+        for prime in prime_range(sieve, 2, n) {
+            acc = acc.checked_mul(&T::from_usize(prime)?)?;
+        }
+        Some(acc)
+    }
+}
+
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone + ToPrimitive> Hyperfactorial<T> for T {
+    fn checked_hyperfactorial(&self) -> Option<T> {
+        let mut acc = T::one();
+        let mut i = T::one();
+        while &i <= self {
+            let power = num_traits::checked_pow(i.clone(), i.to_usize()?)?;
+            acc = acc.checked_mul(&power)?;
+            i = i + T::one();
+        }
+        Some(acc)
+    }
+}
+
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> Superfactorial<T> for T {
+    fn checked_superfactorial(&self) -> Option<T> {
+        // Keep i! alongside the product instead of recomputing it.
+        let mut factorial = T::one();
+        let mut acc = T::one();
+        let mut i = T::one();
+        while &i <= self {
+            factorial = factorial.checked_mul(&i)?;
+            acc = acc.checked_mul(&factorial)?;
+            i = i + T::one();
+        }
+        Some(acc)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + Unsigned + CheckedMul + Clone> FactorialMod<T> for T {
+    fn checked_factorial_mod(&self, modulus: &T) -> Option<T> {
+        modular::checked_factorial_mod(self, modulus)
+    }
+}
+
+impl<T: PartialOrd + Unsigned + Clone> PrimeExponent for T {
+    fn prime_exponent_in_factorial(&self, p: &T) -> T {
+        assert!(*p > T::one(), "p must be at least 2");
+        let mut exponent = T::zero();
+        let mut q = self.clone();
+        while &q >= p {
+            q = q / p.clone();
+            exponent = exponent + q.clone();
+        }
+        exponent
+    }
+}
+
+impl<T: PartialOrd + Unsigned + Clone> TrailingZeros for T {
+    fn trailing_zeros_of_factorial(&self) -> T {
+        let five = T::one() + T::one() + T::one() + T::one() + T::one();
+        self.prime_exponent_in_factorial(&five)
+    }
+
+    fn trailing_zeros_of_factorial_base(&self, base: &T) -> T {
+        assert!(*base > T::one(), "base must be at least 2");
+        let mut zeros: Option<T> = None;
+        for_each_prime_power(base, |p, e| {
+            let count = self.prime_exponent_in_factorial(p) / e;
+            zeros = Some(match zeros.take() {
+                Some(zeros) if zeros < count => zeros,
+                _ => count,
+            });
+        });
+        zeros.expect("base has a prime factor")
+    }
+}
+
+#[cfg(feature = "std")]
+impl Gamma for f64 {
+    fn gamma(&self) -> f64 {
+        gamma::gamma(*self)
+    }
+
+    fn factorial(&self) -> f64 {
+        gamma::gamma(self + 1.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Unsigned + ToPrimitive> ApproxFactorial for T {
+    fn approx_factorial(&self) -> f64 {
+        estimate::stirling_approx(self.to_u64().unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Unsigned + ToPrimitive> LogFactorial for T {
+    fn log_factorial(&self) -> f64 {
+        match self.to_u64() {
+            Some(n) => estimate::ln_factorial(n),
+            None => estimate::stirling_series(self.to_f64().unwrap_or(f64::INFINITY)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        ApproxFactorial, Binomial, DoubleFactorial, Factorial, FactorialMod, FallingFactorial,
+        Hyperfactorial, LogFactorial, Multifactorial, PrimeExponent, Primorial, Subfactorial,
+        Superfactorial, TrailingZeros,
+    };
+    use num_bigint::*;
+    use primal_sieve::Sieve;
+
+    #[test]
+    fn zero_fact_is_one() {
+        assert_eq!(0u32.factorial(), 1u32);
+    }
+
+    #[test]
+    fn one_fact_is_one() {
+        assert_eq!(1.factorial(), 1u32);
+    }
+
+    #[test]
+    fn two_fact_is_two() {
+        assert_eq!(2.factorial(), 2u32);
+    }
+
+    #[test]
+    fn ten_fact() {
+        assert_eq!(10u32.factorial(), 3_628_800);
+    }
+
+    #[test]
+    fn one_hundred_fact() {
+        let sieve = Sieve::new(100);
+        assert_eq!(
+            100.to_biguint().unwrap().factorial(),
+            100.to_biguint().unwrap().psw_factorial(&sieve).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing factorial")]
+    fn too_large() {
+        100u32.factorial();
+    }
+
+    #[test]
+    fn too_large_safe() {
+        assert_eq!(100u32.checked_factorial(), None)
+    }
+
+    /// An unsigned integer with only four bits.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Nibble(u8);
+
+    impl Nibble {
+        fn new(value: u8) -> Nibble {
+            Nibble(value & 0xf)
+        }
+    }
+
+    macro_rules! nibble_op {
+        ($trait:ident, $method:ident, $op:tt) => {
+            impl std::ops::$trait for Nibble {
+                type Output = Nibble;
+                fn $method(self, rhs: Nibble) -> Nibble {
+                    Nibble::new(self.0 $op rhs.0)
+                }
+            }
+        };
+    }
+    nibble_op!(Add, add, +);
+    nibble_op!(Sub, sub, -);
+    nibble_op!(Mul, mul, *);
+    nibble_op!(Div, div, /);
+    nibble_op!(Rem, rem, %);
+
+    impl std::ops::Shl<u32> for Nibble {
+        type Output = Nibble;
+        fn shl(self, rhs: u32) -> Nibble {
+            Nibble::new(self.0 << rhs)
+        }
+    }
+
+    impl num_traits::Zero for Nibble {
+        fn zero() -> Nibble {
+            Nibble(0)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl num_traits::One for Nibble {
+        fn one() -> Nibble {
+            Nibble(1)
+        }
+    }
+
+    impl num_traits::Num for Nibble {
+        type FromStrRadixErr = ();
+        fn from_str_radix(s: &str, radix: u32) -> Result<Nibble, ()> {
+            let value = u8::from_str_radix(s, radix).map_err(|_| ())?;
+            num_traits::FromPrimitive::from_u8(value).ok_or(())
+        }
+    }
+
+    impl num_traits::Unsigned for Nibble {}
+
+    impl num_traits::CheckedMul for Nibble {
+        fn checked_mul(&self, rhs: &Nibble) -> Option<Nibble> {
+            num_traits::FromPrimitive::from_u8(self.0 * rhs.0)
+        }
+    }
+
+    impl num_traits::FromPrimitive for Nibble {
+        fn from_i64(n: i64) -> Option<Nibble> {
+            u64::try_from(n).ok().and_then(Nibble::from_u64)
+        }
+        fn from_u64(n: u64) -> Option<Nibble> {
+            (n < 16).then_some(Nibble(n as u8))
+        }
+    }
+
+    impl num_traits::ToPrimitive for Nibble {
+        fn to_i64(&self) -> Option<i64> {
+            Some(i64::from(self.0))
+        }
+        fn to_u64(&self) -> Option<u64> {
+            Some(u64::from(self.0))
+        }
+    }
+
+    #[test]
+    fn tiny_type_support() {
+        assert_eq!(Nibble(0).checked_factorial(), Some(Nibble(1)));
+        assert_eq!(Nibble(1).checked_factorial(), Some(Nibble(1)));
+        assert_eq!(Nibble(3).checked_factorial(), Some(Nibble(6)));
+        assert_eq!(Nibble(4).checked_factorial(), None);
+        assert_eq!(Nibble(15).checked_factorial(), None);
+        assert_eq!(Nibble(3).psw_factorial(&Sieve::new(10)), Some(Nibble(6)));
+    }
+
+    #[test]
+    fn biguint_support() {
+        assert_eq!(
+            2u32.to_biguint().unwrap().factorial(),
+            2u32.to_biguint().unwrap()
+        );
+        assert_eq!(
+            2u32.to_biguint().unwrap().checked_factorial(),
+            Some(2u32.to_biguint().unwrap())
+        );
+    }
+
+    #[test]
+    fn partial_until_overflow_u32() {
+        assert_eq!(100u32.factorial_partial_until_overflow(), (12, 479_001_600));
+        assert_eq!(12u32.factorial_partial_until_overflow(), (12, 479_001_600));
+        assert_eq!(7u32.factorial_partial_until_overflow(), (7, 5040));
+        assert_eq!(0u32.factorial_partial_until_overflow(), (0, 1));
+    }
+
+    #[test]
+    fn partial_until_overflow_u64() {
+        assert_eq!(
+            100u64.factorial_partial_until_overflow(),
+            (20, 2_432_902_008_176_640_000)
+        );
+        assert_eq!(
+            u64::MAX.factorial_partial_until_overflow(),
+            (20, 2_432_902_008_176_640_000)
+        );
+    }
+
+    #[test]
+    fn partial_until_overflow_biguint() {
+        let n = 200.to_biguint().unwrap();
+        assert_eq!(n.factorial_partial_until_overflow(), (200, n.factorial()));
+    }
+
+    #[test]
+    fn partial_until_overflow_beyond_usize() {
+        let (k, res) = u128::MAX.factorial_partial_until_overflow();
+        assert_eq!((k, res), (34, 34u128.factorial()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Argument too large to compute the factorial until overflow")]
+    fn partial_until_overflow_huge_biguint() {
+        (BigUint::from(1u32) << 70usize).factorial_partial_until_overflow();
+    }
+
+    #[test]
+    fn two_exponent_matches_legendre() {
+        for n in 0..2000u64 {
+            assert_eq!(
+                n.factorial_two_exponent(),
+                crate::number_theory::legendre(n, 2),
+                "mismatch for n = {n}"
+            );
+        }
+        let n = 1000.to_biguint().unwrap();
+        assert_eq!(n.factorial_two_exponent(), 994);
+    }
+
+    #[test]
+    fn zero_double_fact_is_one() {
+        assert_eq!(0.double_factorial(), 1u32)
+    }
+
+    #[test]
+    fn one_double_fact_is_two() {
+        assert_eq!(1.double_factorial(), 1u32)
+    }
+
+    #[test]
+    fn two_double_fact_is_two() {
+        assert_eq!(2.double_factorial(), 2u32)
+    }
+
+    #[test]
+    fn ten_double_fact() {
+        assert_eq!(10u32.double_factorial(), 3840u32);
+    }
+
+    #[test]
+    fn seven_double_fact() {
+        assert_eq!(7u32.double_factorial(), 105u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing double factorial")]
+    fn too_large_double_fact() {
+        100u32.double_factorial();
+    }
+
+    #[test]
+    fn too_large_safe_double_fact() {
+        assert_eq!(100u32.checked_double_factorial(), None)
+    }
+
+    #[test]
+    fn double_factorial_primitive_overflow() {
+        assert_eq!(
+            33u128.checked_double_factorial(),
+            Some(6_332_659_870_762_850_625)
+        );
+        assert_eq!(63u128.checked_double_factorial(), None);
+        assert_eq!(64u128.checked_double_factorial(), None);
+        assert_eq!(1000u64.checked_double_factorial(), None);
+    }
+
+    #[test]
+    fn double_factorials_range_biguint() {
+        let mut single = BigUint::from(1u32);
+        let mut double = [BigUint::from(1u32), BigUint::from(1u32)];
+        for n in 1..1500u32 {
+            single *= n;
+            double[n as usize % 2] *= n;
+            let big = BigUint::from(n);
+            assert_eq!(big.factorial(), single, "factorial mismatch for {n}");
+            assert_eq!(
+                big.double_factorial(),
+                double[n as usize % 2],
+                "double factorial mismatch for {n}"
+            );
         }
-        let sieve = Sieve::new(self.to_usize()?);
-        self.psw_factorial(&sieve)
     }
 
-    #[inline(always)]
-    fn psw_factorial(&self, sieve: &Sieve) -> Option<T> {
-        if self < &T::from_usize(array::SMALL_ODD_SWING.len())? {
-            return self.psw_factorial_with_array();
+    #[test]
+    fn psw_double_factorial_matches_loop() {
+        let sieve = Sieve::new(2000);
+        for n in 0..=2000u32 {
+            let big = BigUint::from(n);
+            assert_eq!(
+                big.psw_double_factorial(&sieve),
+                crate::multifactorial_loop(&big, 2),
+                "mismatch for {n}"
+            );
         }
-        let bytes = self.to_u32()? - self.to_u32()?.count_ones() - 1;
-        let res = self.odd_factorial(sieve)?;
-        res.checked_mul(&T::from_u8(2)?.shl(bytes))
+        assert_eq!(
+            20u64.psw_double_factorial(&sieve),
+            Some(20u64.double_factorial())
+        );
+        assert_eq!(100u64.psw_double_factorial(&sieve), None);
+        assert_eq!(BigUint::from(5000u32).psw_double_factorial(&sieve), None);
     }
-}
 
-impl<
-        T: PartialOrd
-            + Unsigned
-            + CheckedMul
-            + Clone
-            + FromPrimitive
-            + ToPrimitive
-            + Shl<u32, Output = T>,
-    > PrivateFactorial<T> for T
-{
-    fn prime_swing(&self, sieve: &Sieve) -> Option<T> {
-        let n = self.to_usize()?;
-        if n < array::SMALL_ODD_SWING.len() {
-            return T::from_u128(array::SMALL_ODD_SWING[n]);
+    #[test]
+    fn multifactorial_small() {
+        assert_eq!(10u32.multifactorial(3), 280);
+        assert_eq!(10u32.multifactorial(4), 120);
+        assert_eq!(10u32.multifactorial(10), 10);
+        assert_eq!(10u32.multifactorial(11), 10);
+        assert_eq!(0u32.multifactorial(3), 1);
+        assert_eq!(200u8.multifactorial(1000), 200);
+        assert_eq!(0u8.multifactorial(1000), 1);
+        assert_eq!(10u32.checked_multifactorial(0), None);
+    }
+
+    #[test]
+    fn multifactorial_generalizes() {
+        for n in 0..200u32 {
+            let big = BigUint::from(n);
+            assert_eq!(big.multifactorial(1), big.factorial());
+            assert_eq!(big.multifactorial(2), big.double_factorial());
+            if n <= 20 {
+                let n = u64::from(n);
+                assert_eq!(n.checked_multifactorial(1), n.checked_factorial());
+            }
+            let triple = (1..=n)
+                .rev()
+                .step_by(3)
+                .map(BigUint::from)
+                .product::<BigUint>();
+            assert_eq!(big.multifactorial(3), triple, "mismatch for {n}");
         }
-        let sqrt = ((n as f64).sqrt().floor()) as usize;
-        let mut product = T::one();
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing multifactorial")]
+    fn multifactorial_overflow() {
+        100u32.multifactorial(3);
+    }
 
-        for prime in prime_range(sieve, n / 2 + 1, n) {
-            product = product.checked_mul(&T::from_usize(prime)?)?;
+    #[test]
+    fn subfactorial_small() {
+        let expected = [1u32, 0, 1, 2, 9, 44, 265, 1854, 14833];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!((n as u32).subfactorial(), value);
         }
+    }
 
-        for prime in prime_range(sieve, sqrt + 1, n / 3) {
-            if (n / prime) & 1 == 1 {
-                product = product.checked_mul(&T::from_usize(prime)?)?;
-            }
+    #[test]
+    fn subfactorial_biguint() {
+        assert_eq!(
+            BigUint::from(10u32).subfactorial(),
+            BigUint::from(1_334_961u32)
+        );
+        use num_traits::ToPrimitive;
+        // !n is the integer closest to n! / e
+        let n = 30u32;
+        let ratio = BigUint::from(n).factorial().to_f64().unwrap() / std::f64::consts::E;
+        let sub = BigUint::from(n).subfactorial().to_f64().unwrap();
+        assert!((sub - ratio).abs() / ratio < 1e-15);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing subfactorial")]
+    fn subfactorial_overflow() {
+        20u32.subfactorial();
+    }
+
+    #[test]
+    fn primorial_small() {
+        let expected = [
+            1u64, 1, 2, 6, 6, 30, 30, 210, 210, 210, 210, 2310, 2310, 30030,
+        ];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!((n as u64).primorial(), value, "mismatch for {n}");
         }
+        assert_eq!(29u32.checked_primorial(), None);
+        assert_eq!(23u32.checked_primorial(), Some(223_092_870));
+    }
 
-        for prime in prime_range(sieve, 3, sqrt) {
-            let mut p = 1;
-            let mut q = n;
-            loop {
-                q /= prime;
-                if q == 0 {
-                    break;
-                }
-                if q & 1 == 1 {
-                    p *= prime;
-                }
-            }
-            if p > 1 {
-                product = product.checked_mul(&T::from_usize(p)?)?;
+    #[test]
+    fn primorial_biguint_with_sieve() {
+        let sieve = Sieve::new(1000);
+        let mut expected = BigUint::from(1u32);
+        for n in 0..=1000u32 {
+            if n >= 2 && (2..n).all(|d| n % d != 0) {
+                expected *= n;
             }
+            assert_eq!(
+                BigUint::from(n).psw_primorial(&sieve),
+                Some(expected.clone())
+            );
         }
-        Some(product)
+        assert_eq!(2000u32.psw_primorial(&sieve), None);
     }
 
-    fn odd_factorial(&self, sieve: &Sieve) -> Option<T> {
-        let two = T::from_u8(2).unwrap();
-        if self < &(two) {
-            return Some(Self::one());
+    #[test]
+    fn hyperfactorial_small() {
+        let expected = [1u64, 1, 4, 108, 27648, 86_400_000, 4_031_078_400_000];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!((n as u64).hyperfactorial(), value, "mismatch for {n}");
         }
-        let tmp = (self.clone() / two).odd_factorial(sieve)?;
-        let tmp_sq = tmp.checked_mul(&tmp)?;
-        tmp_sq.checked_mul(&self.prime_swing(sieve)?)
+        assert_eq!(4u32.checked_hyperfactorial(), Some(27648));
+        assert_eq!(5u32.checked_hyperfactorial(), Some(86_400_000));
+        assert_eq!(6u32.checked_hyperfactorial(), None);
+        assert_eq!(8u64.checked_hyperfactorial(), None);
     }
 
-    fn odd_factorial_array(&self) -> Option<T> {
-        let two = T::from_u8(2).unwrap();
-        if self < &(two) {
-            return Some(Self::one());
+    #[test]
+    fn hyperfactorial_biguint() {
+        assert_eq!(
+            BigUint::from(4u32).hyperfactorial(),
+            BigUint::from(27648u32)
+        );
+        let mut expected = BigUint::from(1u32);
+        for n in 1..=60u32 {
+            expected *= num_traits::pow(BigUint::from(n), n as usize);
+            assert_eq!(
+                BigUint::from(n).hyperfactorial(),
+                expected,
+                "mismatch for {n}"
+            );
         }
-        let tmp = (self.clone() / two).odd_factorial_array()?;
-        let tmp_sq = tmp.checked_mul(&tmp)?;
-        tmp_sq.checked_mul(&T::from_u128(array::SMALL_ODD_SWING[self.to_usize()?])?)
     }
 
-    fn psw_factorial_with_array(&self) -> Option<T> {
-        if self < &T::from_usize(array::SMALL_FACTORIAL.len()).unwrap() {
-            return T::from_u128(array::SMALL_FACTORIAL[self.to_usize().unwrap()]);
+    #[test]
+    #[should_panic(expected = "Overflow computing hyperfactorial")]
+    fn hyperfactorial_overflow() {
+        10u32.hyperfactorial();
+    }
+
+    #[test]
+    fn superfactorial_small() {
+        let expected = [1u64, 1, 2, 12, 288, 34560, 24_883_200, 125_411_328_000];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!((n as u64).superfactorial(), value, "mismatch for {n}");
         }
-        let bytes = self.to_u32()? - self.to_u32()?.count_ones() - 1;
-        let res = self.odd_factorial_array()?;
-        res.checked_mul(&T::from_u8(2)?.shl(bytes))
+        assert_eq!(6u32.checked_superfactorial(), Some(24_883_200));
+        assert_eq!(7u32.checked_superfactorial(), None);
     }
-}
 
-impl<T: PartialOrd + Unsigned + CheckedMul + Copy> DoubleFactorial<T> for T {
-    #[inline(always)]
-    fn checked_double_factorial(&self) -> Option<T> {
-        let one = T::one();
-        let two = one + one;
-        let mut acc = one;
-        let mut i = if *self % two == T::zero() { two } else { one };
-        while i <= *self {
-            if let Some(acc_i) = acc.checked_mul(&i) {
-                acc = acc_i;
-                i = i + two;
-            } else {
-                return None;
+    #[test]
+    fn superfactorial_biguint() {
+        assert_eq!(BigUint::from(4u32).superfactorial(), BigUint::from(288u32));
+        let mut expected = BigUint::from(1u32);
+        for n in 1..=100u32 {
+            expected *= BigUint::from(n).factorial();
+            assert_eq!(
+                BigUint::from(n).superfactorial(),
+                expected,
+                "mismatch for {n}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing superfactorial")]
+    fn superfactorial_overflow() {
+        10u32.superfactorial();
+    }
+
+    #[test]
+    fn factorial_mod_small() {
+        assert_eq!(10u32.factorial_mod(&1000), 800);
+        assert_eq!(0u32.factorial_mod(&1), 0);
+        assert_eq!(0u32.factorial_mod(&7), 1);
+        assert_eq!(5u8.factorial_mod(&5), 0);
+        assert_eq!(4u8.factorial_mod(&251), 24);
+        assert_eq!(3u32.checked_factorial_mod(&0), None);
+        for n in 0..=20u64 {
+            for m in [2u64, 97, 1000, 1_000_000_007] {
+                assert_eq!(n.factorial_mod(&m), n.factorial() % m);
             }
         }
-        Some(acc)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{DoubleFactorial, Factorial};
-    use num_bigint::*;
-    use primal_sieve::Sieve;
+    #[test]
+    fn factorial_mod_large() {
+        // 1000! overflows any primitive, 1000! mod p doesn't.
+        let p = 1_000_000_007u64;
+        let expected = BigUint::from(1000u32).factorial() % BigUint::from(p);
+        assert_eq!(BigUint::from(1000u64.factorial_mod(&p)), expected);
+        // Products of residues near 2^64 take the slow path.
+        let p = 18_446_744_073_709_551_557u64;
+        let expected = BigUint::from(300u32).factorial() % BigUint::from(p);
+        assert_eq!(BigUint::from(300u64.factorial_mod(&p)), expected);
+        let p = u128::MAX - 158;
+        let expected = BigUint::from(100u32).factorial() % BigUint::from(p);
+        assert_eq!(BigUint::from(100u128.factorial_mod(&p)), expected);
+    }
 
     #[test]
-    fn zero_fact_is_one() {
-        assert_eq!(0u32.factorial(), 1u32);
+    #[should_panic(expected = "Zero modulus computing factorial mod")]
+    fn factorial_mod_zero_modulus() {
+        5u32.factorial_mod(&0);
     }
 
     #[test]
-    fn one_fact_is_one() {
-        assert_eq!(1.factorial(), 1u32);
+    fn prime_exponent_in_factorial() {
+        use num_traits::{ToPrimitive, Zero};
+        assert_eq!(10u32.prime_exponent_in_factorial(&2), 8);
+        assert_eq!(25u32.prime_exponent_in_factorial(&5), 6);
+        assert_eq!(4u8.prime_exponent_in_factorial(&5), 0);
+        assert_eq!(u64::MAX.prime_exponent_in_factorial(&2), u64::MAX - 64);
+        let n = BigUint::from(1000u32);
+        let factorial = n.factorial();
+        for p in [2u32, 3, 7, 997] {
+            let p = BigUint::from(p);
+            let e = n.prime_exponent_in_factorial(&p);
+            let power = num_traits::pow(p.clone(), e.to_usize().unwrap());
+            assert_eq!(&factorial % &power, BigUint::zero());
+            assert_ne!(&factorial % (power * &p), BigUint::zero());
+        }
     }
 
     #[test]
-    fn two_fact_is_two() {
-        assert_eq!(2.factorial(), 2u32);
+    #[should_panic(expected = "p must be at least 2")]
+    fn prime_exponent_of_one() {
+        10u32.prime_exponent_in_factorial(&1);
     }
 
     #[test]
-    fn ten_fact() {
-        assert_eq!(10u32.factorial(), 3_628_800);
+    fn trailing_zeros_of_factorial() {
+        assert_eq!(100u32.trailing_zeros_of_factorial(), 24);
+        assert_eq!(4u8.trailing_zeros_of_factorial(), 0);
+        for n in 0..=300u32 {
+            let digits = BigUint::from(n).factorial().to_string();
+            let zeros = digits.len() - digits.trim_end_matches('0').len();
+            assert_eq!(n.trailing_zeros_of_factorial() as usize, zeros);
+            assert_eq!(n.trailing_zeros_of_factorial_base(&10), zeros as u32);
+        }
     }
 
     #[test]
-    fn one_hundred_fact() {
-        let sieve = Sieve::new(100);
-        assert_eq!(
-            100.to_biguint().unwrap().factorial(),
-            100.to_biguint().unwrap().psw_factorial(&sieve).unwrap()
-        );
+    fn trailing_zeros_of_factorial_base() {
+        for n in 0..=60u64 {
+            let factorial = BigUint::from(n).factorial();
+            for base in 2..=40u64 {
+                let mut rest = factorial.clone();
+                let mut zeros = 0;
+                while (&rest % base).bits() == 0 {
+                    rest /= base;
+                    zeros += 1;
+                }
+                assert_eq!(
+                    n.trailing_zeros_of_factorial_base(&base),
+                    zeros,
+                    "mismatch for n = {n}, base = {base}"
+                );
+            }
+        }
+        // A prime base past the square root check, and large prime powers.
+        assert_eq!(100u64.trailing_zeros_of_factorial_base(&97), 1);
+        assert_eq!(100u64.trailing_zeros_of_factorial_base(&(1 << 63)), 1);
+        assert_eq!(u8::MAX.trailing_zeros_of_factorial_base(&u8::MAX), 15);
     }
 
     #[test]
-    #[should_panic(expected = "Overflow computing factorial")]
-    fn too_large() {
-        100u32.factorial();
+    #[should_panic(expected = "base must be at least 2")]
+    fn trailing_zeros_of_factorial_base_one() {
+        10u32.trailing_zeros_of_factorial_base(&1);
     }
 
     #[test]
-    fn too_large_safe() {
-        assert_eq!(100u32.checked_factorial(), None)
+    fn approx_factorial_accuracy() {
+        use num_traits::ToPrimitive;
+        assert_eq!(0u32.approx_factorial(), 1.0);
+        for n in 1..=170u32 {
+            let exact = BigUint::from(n).factorial().to_f64().unwrap();
+            let err = (n.approx_factorial() / exact - 1.0).abs();
+            assert!(err < 1e-3, "n = {n}: relative error {err}");
+            assert!(err < 3e-4 / f64::from(n).powi(4) + 1e-13, "n = {n}: {err}");
+        }
+        assert_eq!(171u32.approx_factorial(), f64::INFINITY);
+        assert_eq!(u64::MAX.approx_factorial(), f64::INFINITY);
     }
 
     #[test]
-    fn biguint_support() {
-        assert_eq!(
-            2u32.to_biguint().unwrap().factorial(),
-            2u32.to_biguint().unwrap()
-        );
+    fn log_factorial_small() {
+        for n in 0..=20u64 {
+            let expected = (n.factorial() as f64).ln();
+            assert!((n.log_factorial() - expected).abs() <= 1e-10 * expected.max(1.0));
+        }
+        assert!((5u8.log_factorial_base(2.0) - 120f64.log2()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn log_factorial_large() {
+        use num_traits::ToPrimitive;
+        // ln(n!) from the exact value, scaled into the range of f64
+        for n in [35u32, 100, 1000, 10_000] {
+            let exact = BigUint::from(n).factorial();
+            let shift = exact.bits() - 64;
+            let expected =
+                (&exact >> shift).to_f64().unwrap().ln() + shift as f64 * std::f64::consts::LN_2;
+            let err = ((n.log_factorial() - expected) / expected).abs();
+            assert!(err < 1e-10, "n = {n}: relative error {err}");
+        }
+        let huge: BigUint = BigUint::from(1u32) << 70u32;
+        assert!(huge.log_factorial().is_finite());
+    }
+
+    #[test]
+    fn falling_factorial_small() {
+        assert_eq!(10u32.falling_factorial(&0), 1);
+        assert_eq!(10u32.falling_factorial(&3), 720);
+        assert_eq!(10u32.falling_factorial(&10), 3_628_800);
+        assert_eq!(10u32.falling_factorial(&11), 0);
+        assert_eq!(0u32.falling_factorial(&0), 1);
+    }
+
+    #[test]
+    fn falling_factorial_large_n() {
+        // 1259! is far out of reach, but four terms fit easily.
         assert_eq!(
-            2u32.to_biguint().unwrap().checked_factorial(),
-            Some(2u32.to_biguint().unwrap())
+            1259u64.checked_falling_factorial(&4),
+            Some(1259 * 1258 * 1257 * 1256)
         );
+        assert_eq!(u64::MAX.checked_falling_factorial(&1), Some(u64::MAX));
+        assert_eq!(u64::MAX.checked_falling_factorial(&2), None);
     }
 
     #[test]
-    fn zero_double_fact_is_one() {
-        assert_eq!(0.double_factorial(), 1u32)
+    fn falling_factorial_matches_ratio() {
+        for n in 0..60u32 {
+            let big_n = BigUint::from(n);
+            for k in 0..=n {
+                let expected = big_n.factorial() / BigUint::from(n - k).factorial();
+                assert_eq!(big_n.falling_factorial(&BigUint::from(k)), expected);
+            }
+        }
     }
 
     #[test]
-    fn one_double_fact_is_two() {
-        assert_eq!(1.double_factorial(), 1u32)
+    fn falling_factorial_across_types() {
+        // P(n, k) term by term must agree with n! / (n - k)! for every type
+        // that can hold it; 0.3.0 returned 51 for 1259! / 1255!.
+        fn check<T>(n: u32, k: u32, expected: &BigUint)
+        where
+            T: FallingFactorial + TryFrom<u32> + TryFrom<BigUint> + PartialEq + std::fmt::Debug,
+            <T as TryFrom<u32>>::Error: std::fmt::Debug,
+        {
+            let n_t = T::try_from(n).unwrap();
+            let k_t = T::try_from(k).unwrap();
+            let actual = n_t.checked_falling_factorial(&k_t);
+            assert_eq!(actual, T::try_from(expected.clone()).ok(), "P({n}, {k})");
+        }
+
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = |bound: u32| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % (u64::from(bound) + 1)) as u32
+        };
+        for _ in 0..2000 {
+            let n = next(200);
+            let k = next(n);
+            let expected = BigUint::from(n).factorial() / BigUint::from(n - k).factorial();
+            assert_eq!(
+                BigUint::from(n).falling_factorial(&BigUint::from(k)),
+                expected
+            );
+            check::<u32>(n, k, &expected);
+            check::<u64>(n, k, &expected);
+            check::<u128>(n, k, &expected);
+            check::<usize>(n, k, &expected);
+        }
+        let expected = BigUint::from(1259u32).factorial() / BigUint::from(1255u32).factorial();
+        check::<u64>(1259, 4, &expected);
+        check::<BigUint>(1259, 4, &expected);
     }
 
     #[test]
-    fn two_double_fact_is_two() {
-        assert_eq!(2.double_factorial(), 2u32)
+    #[should_panic(expected = "Overflow computing falling factorial")]
+    fn falling_factorial_overflow() {
+        100u32.falling_factorial(&10);
     }
 
     #[test]
-    fn ten_double_fact() {
-        assert_eq!(10u32.double_factorial(), 3840u32);
+    fn binomial_small() {
+        assert_eq!(5u32.binomial(&0), 1);
+        assert_eq!(5u32.binomial(&2), 10);
+        assert_eq!(5u32.binomial(&5), 1);
+        assert_eq!(5u32.binomial(&6), 0);
+        assert_eq!(0u32.binomial(&0), 1);
     }
 
     #[test]
-    fn seven_double_fact() {
-        assert_eq!(7u32.double_factorial(), 105u32);
+    fn binomial_beyond_factorial_range() {
+        assert_eq!(60u128.checked_factorial(), None);
+        assert_eq!(60u128.checked_binomial(&30), Some(118_264_581_564_861_424));
+        assert_eq!(1000u64.checked_binomial(&2), Some(499_500));
+        assert_eq!(1000u64.checked_binomial(&998), Some(499_500));
     }
 
     #[test]
-    #[should_panic(expected = "Overflow computing double factorial")]
-    fn too_large_double_fact() {
-        100u32.double_factorial();
+    fn binomial_pascal_biguint() {
+        let mut row = vec![BigUint::from(1u32)];
+        for n in 1..=200u32 {
+            let mut next = vec![BigUint::from(1u32); n as usize + 1];
+            for k in 1..n as usize {
+                next[k] = &row[k - 1] + &row[k];
+            }
+            row = next;
+            for (k, expected) in row.iter().enumerate() {
+                assert_eq!(BigUint::from(n).binomial(&BigUint::from(k)), *expected);
+            }
+        }
     }
 
     #[test]
-    fn too_large_safe_double_fact() {
-        assert_eq!(100u32.checked_double_factorial(), None)
+    #[should_panic(expected = "Overflow computing binomial coefficient")]
+    fn binomial_overflow() {
+        100u32.binomial(&50);
     }
 
     #[test]
@@ -322,6 +2070,250 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gamma_of_floats() {
+        use crate::Gamma;
+        assert!((Gamma::gamma(&5.0) - 24.0).abs() < 1e-12);
+        assert!((Gamma::gamma(&0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-14);
+        // Γ(-1/2) = -2√π by the reflection formula
+        assert!((Gamma::gamma(&-0.5) + 2.0 * std::f64::consts::PI.sqrt()).abs() < 1e-13);
+        assert!(Gamma::gamma(&-2.0).is_nan());
+        for n in 0..=20u64 {
+            let exact = n.factorial() as f64;
+            let err = ((n as f64).factorial() - exact).abs() / exact;
+            assert!(err < 1e-12, "n = {n}: relative error {err}");
+        }
+        assert_eq!(200.0.factorial(), f64::INFINITY);
+    }
+
+    #[test]
+    fn signed_factorial() {
+        use crate::SignedFactorial;
+        assert_eq!((-3i32).checked_factorial(), None);
+        assert_eq!(5i32.factorial(), 120);
+        assert_eq!(0i8.factorial(), 1);
+        assert_eq!(5i8.checked_factorial(), Some(120));
+        assert_eq!(6i8.checked_factorial(), None);
+        assert_eq!(i64::MIN.checked_factorial(), None);
+        for n in 0..=40i128 {
+            let expected = (n as u128)
+                .checked_factorial()
+                .and_then(|f| i128::try_from(f).ok());
+            assert_eq!(n.checked_factorial(), expected, "mismatch for {n}");
+        }
+        // 33! fits in an i128, 34! only in a u128.
+        assert!(33i128.checked_factorial().is_some());
+        assert_eq!(34i128.checked_factorial(), None);
+    }
+
+    #[test]
+    fn signed_factorial_bigint() {
+        use crate::SignedFactorial;
+        use num_bigint::{BigInt, ToBigInt};
+        assert_eq!(5.to_bigint().unwrap().factorial(), BigInt::from(120));
+        assert_eq!((-1).to_bigint().unwrap().checked_factorial(), None);
+        assert_eq!(BigInt::from(0).factorial(), BigInt::from(1));
+        let n = BigUint::from(300u32);
+        assert_eq!(
+            BigInt::from(300).factorial(),
+            BigInt::from(Factorial::factorial(&n))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "factorial of negative number is undefined")]
+    fn signed_factorial_bigint_negative() {
+        use crate::SignedFactorial;
+        num_bigint::BigInt::from(-5).factorial();
+    }
+
+    #[test]
+    #[should_panic(expected = "factorial of negative number is undefined")]
+    fn signed_factorial_negative() {
+        use crate::SignedFactorial;
+        (-1i64).factorial();
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow computing factorial")]
+    fn signed_factorial_overflow() {
+        use crate::SignedFactorial;
+        21i64.factorial();
+    }
+
+    #[test]
+    fn try_factorial_errors() {
+        use crate::{FactorialError, SignedFactorial};
+        assert_eq!(12u32.try_factorial(), Ok(479_001_600));
+        assert_eq!(13u32.try_factorial(), Err(FactorialError::Overflow));
+        assert_eq!((-1i64).try_factorial(), Err(FactorialError::Negative));
+        assert_eq!(21i64.try_factorial(), Err(FactorialError::Overflow));
+        assert_eq!(
+            num_bigint::BigInt::from(-7).try_factorial(),
+            Err(FactorialError::Negative)
+        );
+
+        let sieve = Sieve::new(1000);
+        let have = sieve.upper_bound();
+        let needed = have + 1;
+        assert_eq!(
+            BigUint::from(needed).try_psw_factorial(&sieve),
+            Err(FactorialError::SieveTooSmall { needed, have })
+        );
+        assert_eq!(
+            BigUint::from(1000u32).try_psw_factorial(&sieve),
+            Ok(BigUint::from(1000u32).factorial())
+        );
+        assert_eq!(
+            200u64.try_psw_factorial(&sieve),
+            Err(FactorialError::Overflow)
+        );
+        assert_eq!(
+            (BigUint::from(1u32) << 200usize).try_psw_factorial(&sieve),
+            Err(FactorialError::Overflow)
+        );
+        assert_eq!(
+            FactorialError::SieveTooSmall { needed: 5, have: 3 }.to_string(),
+            "sieve up to 3 is too small for the factorial of 5"
+        );
+    }
+
+    #[test]
+    fn free_functions_forward() {
+        assert_eq!(crate::factorial(10u32), 10u32.factorial());
+        assert_eq!(crate::checked_factorial(13u32), None);
+        assert_eq!(
+            crate::checked_factorial(BigUint::from(100u32)),
+            Some(BigUint::from(100u32).factorial())
+        );
+        assert_eq!(crate::double_factorial(10u64), 3840);
+    }
+
+    #[test]
+    fn const_factorial_small() {
+        const X: u128 = crate::const_factorial(20);
+        assert_eq!(X, 20u128.factorial());
+        for n in 0..=34u32 {
+            assert_eq!(crate::const_factorial(n), u128::from(n).factorial());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n! overflows u128 for n > 34")]
+    fn const_factorial_too_large() {
+        crate::const_factorial(35);
+    }
+
+    #[test]
+    fn small_table_thresholds_agree() {
+        use crate::{array, PrivateFactorial, SMALL_SWING_LEN};
+        // The factorial table must cover a prefix of the swing table.
+        assert!(array::SMALL_FACTORIAL.len() <= SMALL_SWING_LEN);
+        let sieve = Sieve::new(2 * SMALL_SWING_LEN);
+        for n in 0..SMALL_SWING_LEN + 5 {
+            let big = BigUint::from(n);
+            let expected = big.naive_factorial();
+            assert_eq!(big.checked_factorial(), expected, "n = {n}");
+            assert_eq!(big.psw_factorial(&sieve), expected, "n = {n}");
+            if n < SMALL_SWING_LEN {
+                assert_eq!(big.psw_factorial_with_array(), expected, "n = {n}");
+            }
+            // The odd part of n! / (n / 2)!^2, from the table below the
+            // threshold and from the sieve above it.
+            let swing = expected.unwrap() / num_traits::pow(BigUint::from(n / 2).factorial(), 2);
+            let odd_swing = &swing >> swing.trailing_zeros().unwrap_or(0);
+            assert_eq!(big.prime_swing(&sieve), Some(odd_swing), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn psw_checked_adequate_sieve() {
+        let sieve = Sieve::new(1000);
+        let n = 1000.to_biguint().unwrap();
+        assert_eq!(n.psw_factorial_checked(&sieve, 1000), Some(n.factorial()));
+        assert_eq!(
+            20u64.psw_factorial_checked(&sieve, 20),
+            Some(20u64.factorial())
+        );
+    }
+
+    #[test]
+    fn psw_checked_inadequate_sieve() {
+        let sieve = Sieve::new(100);
+        let n = 1000.to_biguint().unwrap();
+        assert_eq!(n.psw_factorial_checked(&sieve, 100), None);
+        // The sieve covers 200, but the caller only vouches for 150.
+        let n = 200.to_biguint().unwrap();
+        assert_eq!(n.psw_factorial_checked(&Sieve::new(200), 150), None);
+        // A bound larger than the actual sieve does not cause a panic.
+        assert_eq!(n.psw_factorial_checked(&sieve, 200), None);
+    }
+
+    #[test]
+    fn factorial_with_sieve_reuses_sieve() {
+        let (res, sieve) = 500u32.to_biguint().unwrap().factorial_with_sieve(None);
+        assert_eq!(res, Some(500u32.to_biguint().unwrap().factorial()));
+        let bound = sieve.upper_bound();
+        assert!(bound >= 500);
+
+        // A covered argument keeps the very same sieve.
+        let (res, sieve) = 20u64.factorial_with_sieve(Some(sieve));
+        assert_eq!(res, Some(20u64.factorial()));
+        assert_eq!(sieve.upper_bound(), bound);
+        let n = bound.to_biguint().unwrap();
+        let (res, sieve) = n.factorial_with_sieve(Some(sieve));
+        assert_eq!(res, Some(n.factorial()));
+        assert_eq!(sieve.upper_bound(), bound);
+
+        // A larger one grows it geometrically rather than just to fit.
+        let n = (bound + 1).to_biguint().unwrap();
+        let (res, sieve) = n.factorial_with_sieve(Some(sieve));
+        assert_eq!(res, Some(n.factorial()));
+        assert!(sieve.upper_bound() >= 2 * bound);
+
+        // Overflow and huge arguments still hand the sieve back.
+        let (res, sieve) = 100u64.factorial_with_sieve(Some(sieve));
+        assert_eq!(res, None);
+        assert!(sieve.upper_bound() >= 2 * bound);
+        let (res, _) = (BigUint::from(1u32) << 200usize).factorial_with_sieve(None);
+        assert_eq!(res, None);
+    }
+
+    #[test]
+    fn psw_inadequate_sieve() {
+        let sieve = Sieve::new(10);
+        let n = 1000.to_biguint().unwrap();
+        assert_eq!(n.psw_factorial(&sieve), None);
+        assert_eq!(1000u128.psw_factorial(&sieve), None);
+        assert_eq!(
+            10u32.psw_factorial(&sieve),
+            Some(3628800),
+            "the sieve covers the argument"
+        );
+        // Even where the small tables would not need the sieve.
+        assert_eq!(20u64.psw_factorial(&sieve), None);
+    }
+
+    #[test]
+    fn reproducible_across_runs_and_threads() {
+        // The multiplication order only depends on the argument, so the
+        // result must be byte-identical no matter where it is computed.
+        let n = 5000.to_biguint().unwrap();
+        let expected = n.factorial().to_bytes_be();
+        for _ in 0..5 {
+            assert_eq!(n.factorial().to_bytes_be(), expected);
+        }
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let n = n.clone();
+                std::thread::spawn(move || n.factorial().to_bytes_be())
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+
     #[test]
     fn crazy_big_factorial() {
         let sieve = Sieve::new(8000);
@@ -334,3 +2326,39 @@ mod tests {
         assert_eq!(p_prime, p, "mismatch for iteration {n}");
     }
 }
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use crate::{array, DoubleFactorial, Factorial};
+
+    #[test]
+    fn factorials_from_the_table() {
+        for (n, &expected) in array::SMALL_FACTORIAL.iter().enumerate() {
+            assert_eq!((n as u128).checked_factorial(), Some(expected));
+        }
+        assert_eq!(20u64.checked_factorial(), Some(2_432_902_008_176_640_000));
+        assert_eq!(12u32.factorial(), 479_001_600);
+    }
+
+    #[test]
+    fn none_beyond_the_table() {
+        // Without a sieve nothing past 34! is computed, even in types where
+        // it isn't an overflow of the table itself.
+        for n in 35..200u128 {
+            assert_eq!(n.checked_factorial(), None);
+        }
+        assert_eq!(21u64.checked_factorial(), None);
+        assert_eq!(13u32.checked_factorial(), None);
+    }
+
+    #[test]
+    fn double_factorials() {
+        assert_eq!(0u32.checked_double_factorial(), Some(1));
+        assert_eq!(9u32.checked_double_factorial(), Some(945));
+        assert_eq!(10u32.checked_double_factorial(), Some(3840));
+        assert!(33u64.checked_double_factorial().is_some());
+        assert_eq!(34u64.checked_double_factorial(), None);
+        assert!(56u128.checked_double_factorial().is_some());
+        assert_eq!(57u128.checked_double_factorial(), None);
+    }
+}
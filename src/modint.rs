@@ -0,0 +1,77 @@
+//! Factorials in the modular integer types of `num-modular`.
+
+use num_modular::ModularInteger;
+use num_traits::One;
+
+/// Computes factorials inside the ring of a `num-modular` integer.
+///
+/// Modular integers never overflow, so they don't fit the bounds of
+/// [`Factorial`](crate::Factorial). Instead, the value the method is called on
+/// only selects the ring, i.e. the modulus and the reduction strategy, such as
+/// `MontgomeryInt` or `VanillaInt`, and the factorial of a plain integer is
+/// computed in it.
+///
+/// # Examples
+/// ```
+/// use factorial::ModularFactorial;
+/// use num_modular::{ModularInteger, MontgomeryInt};
+/// let ring = MontgomeryInt::new(0u64, &1_000_000_007);
+/// assert_eq!(ring.modular_factorial(20).residue(), 146326063);
+/// ```
+pub trait ModularFactorial: ModularInteger {
+    /// Returns `n!` reduced in the ring of `self`.
+    fn modular_factorial(&self, n: u64) -> Self;
+}
+
+impl<M> ModularFactorial for M
+where
+    M: ModularInteger + Clone,
+    M::Base: One,
+{
+    fn modular_factorial(&self, n: u64) -> M {
+        let one = self.convert(M::Base::one());
+        let mut acc = one.clone();
+        let mut i = one.clone();
+        for _ in 1..n {
+            if acc.is_zero() {
+                break;
+            }
+            i = i + one.clone();
+            acc = acc * i.clone();
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MontgomeryFactorial;
+    use num_modular::{MontgomeryInt, VanillaInt};
+
+    #[test]
+    fn montgomery_matches_factorial_mod() {
+        for p in [3u64, 101, 65_537, 1_000_000_007] {
+            let ring = MontgomeryInt::new(0u64, &p);
+            let ctx = MontgomeryFactorial::new(p);
+            for n in [0u64, 1, 2, 5, 20, 100, 1000] {
+                assert_eq!(
+                    ring.modular_factorial(n).residue(),
+                    ctx.factorial_mod(n),
+                    "mismatch for n = {n}, p = {p}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn vanilla_composite_modulus() {
+        let ring = VanillaInt::new(0u32, &1_000_000);
+        // 20! = 2432902008176640000
+        assert_eq!(ring.modular_factorial(20).residue(), 640_000);
+        // 25! ends in six zeros
+        assert_eq!(ring.modular_factorial(25).residue(), 0);
+        assert_eq!(ring.modular_factorial(9).residue(), 362_880);
+        assert_eq!(ring.modular_factorial(0).residue(), 1);
+    }
+}
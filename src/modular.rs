@@ -0,0 +1,732 @@
+//! Factorials reduced modulo some number.
+
+use num_bigint::BigUint;
+use num_traits::{CheckedMul, FromPrimitive, One, ToPrimitive, Unsigned, Zero};
+
+/// Returns `a * b mod m`.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// Returns `base^exponent mod m`.
+fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut acc = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            acc = mul_mod(acc, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    acc
+}
+
+/// Returns `a * b mod m` for `a, b < m`, falling back to doubling and adding
+/// if the product overflows `T`.
+fn checked_mul_mod<T: PartialOrd + Unsigned + CheckedMul + Clone>(a: &T, b: &T, m: &T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m.clone();
+    }
+    let add_mod = |x: T, y: T| {
+        // x + y might overflow, x - (m - y) can't.
+        let gap = m.clone() - y.clone();
+        if x >= gap {
+            x - gap
+        } else {
+            x + y
+        }
+    };
+    let two = T::one() + T::one();
+    let (mut acc, mut a, mut b) = (T::zero(), a.clone(), b.clone());
+    while !b.is_zero() {
+        if (b.clone() % two.clone()).is_one() {
+            acc = add_mod(acc, a.clone());
+        }
+        a = add_mod(a.clone(), a);
+        b = b / two.clone();
+    }
+    acc
+}
+
+/// Returns `n! mod m`, or `None` if `m` is zero.
+pub(crate) fn checked_factorial_mod<T>(n: &T, m: &T) -> Option<T>
+where
+    T: PartialOrd + Unsigned + CheckedMul + Clone,
+{
+    if m.is_zero() {
+        return None;
+    }
+    if n >= m {
+        // m itself is one of the factors.
+        return Some(T::zero());
+    }
+    let mut acc = T::one() % m.clone();
+    let mut i = T::one();
+    while &i <= n {
+        acc = checked_mul_mod(&acc, &i, m);
+        i = i + T::one();
+    }
+    Some(acc)
+}
+
+/// Returns the inverse of `a` modulo `m`, if `a` and `m` are coprime.
+fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    let (mut old_r, mut r) = (i128::from(a % m), i128::from(m));
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    if old_r != 1 {
+        return None;
+    }
+    Some(old_s.rem_euclid(i128::from(m)) as u64)
+}
+
+/// Reduces `x` modulo the Mersenne number `mask = 2^p - 1`.
+///
+/// Since `2^p ≡ 1 (mod 2^p - 1)` the high bits can simply be folded onto the
+/// low bits, so no division is needed.
+fn reduce_mersenne(mut x: BigUint, p: u32, mask: &BigUint) -> BigUint {
+    while x.bits() > u64::from(p) {
+        x = (&x & mask) + (x >> p);
+    }
+    if &x == mask {
+        x.set_zero();
+    }
+    x
+}
+
+/// Returns `n! mod (2^p - 1)`.
+///
+/// The reduction after every multiplication is a shift and an addition
+/// instead of a general division, which makes this considerably faster than
+/// reducing by an arbitrary modulus of the same size.
+///
+/// # Panics
+/// Panics if `p == 0`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_mod_mersenne;
+/// // 10! = 3628800 and 2^7 - 1 = 127
+/// assert_eq!(factorial_mod_mersenne(10, 7), (3628800u32 % 127).into());
+/// ```
+pub fn factorial_mod_mersenne(n: usize, p: u32) -> BigUint {
+    assert!(p > 0, "Mersenne exponent must be positive");
+    let mask = (BigUint::one() << p) - 1u32;
+    if mask.is_one() || BigUint::from(n) >= mask {
+        // The modulus itself is one of the factors.
+        return BigUint::zero();
+    }
+    let mut acc = BigUint::one();
+    for i in 2..=n {
+        acc = reduce_mersenne(acc * i, p, &mask);
+    }
+    acc
+}
+
+/// Computes factorials modulo a fixed odd modulus `p` using Montgomery
+/// multiplication.
+///
+/// Every step of the product then costs two multiplications and no division,
+/// which pays off when computing many factorials modulo the same prime.
+///
+/// # Examples
+/// ```
+/// use factorial::MontgomeryFactorial;
+/// let ctx = MontgomeryFactorial::new(1_000_000_007);
+/// assert_eq!(ctx.factorial_mod(10), 3628800);
+/// assert_eq!(ctx.factorial_mod(20), 146326063);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MontgomeryFactorial {
+    p: u64,
+    /// `-p^(-1) mod 2^64`
+    p_neg_inv: u64,
+    /// `2^64 mod p`, which is `1` in Montgomery form
+    one: u64,
+}
+
+impl MontgomeryFactorial {
+    /// Prepares the Montgomery constants for the modulus `p`.
+    ///
+    /// # Panics
+    /// Panics if `p` is even, `1`, or not smaller than `2^63`.
+    pub fn new(p: u64) -> Self {
+        assert!(
+            p & 1 == 1 && p > 1,
+            "modulus must be odd and greater than one"
+        );
+        assert!(p < 1 << 63, "modulus must be smaller than 2^63");
+        // Newton iteration doubles the number of correct low bits every step.
+        let mut inv = p;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        }
+        MontgomeryFactorial {
+            p,
+            p_neg_inv: inv.wrapping_neg(),
+            one: ((1u128 << 64) % u128::from(p)) as u64,
+        }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// Returns `a * b * 2^(-64) mod p` for `a, b < p`.
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        let t = u128::from(a) * u128::from(b);
+        let m = (t as u64).wrapping_mul(self.p_neg_inv);
+        let u = ((t + u128::from(m) * u128::from(self.p)) >> 64) as u64;
+        if u >= self.p {
+            u - self.p
+        } else {
+            u
+        }
+    }
+
+    /// Returns `n! mod p`.
+    pub fn factorial_mod(&self, n: u64) -> u64 {
+        if n >= self.p {
+            // p is one of the factors.
+            return 0;
+        }
+        let mut acc = self.one;
+        let mut i = self.one;
+        for _ in 2..=n {
+            i += self.one;
+            if i >= self.p {
+                i -= self.p;
+            }
+            acc = self.mul(acc, i);
+        }
+        // Multiplying by 1 converts out of Montgomery form.
+        self.mul(acc, 1)
+    }
+}
+
+/// Answers binomial coefficient and permutation queries modulo a prime `p`
+/// in constant time, after precomputing the factorials and their inverses up
+/// to `max_n` once.
+///
+/// # Examples
+/// ```
+/// use factorial::BinomialModTable;
+/// let table = BinomialModTable::new(1000, 1_000_000_007);
+/// assert_eq!(table.binomial(10, 3), 120);
+/// assert_eq!(table.permutations(10, 3), 720);
+/// assert_eq!(table.binomial(1000, 500), 159835829);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BinomialModTable {
+    p: u64,
+    factorials: Vec<u64>,
+    inverse_factorials: Vec<u64>,
+}
+
+impl BinomialModTable {
+    /// Precomputes `i! mod p` and `(i!)^(-1) mod p` for `i <= max_n`.
+    ///
+    /// # Panics
+    /// Panics if `p` isn't a prime greater than `max_n`, as some factorial
+    /// would then not be invertible.
+    pub fn new(max_n: usize, p: u64) -> Self {
+        assert!(p as u128 > max_n as u128, "modulus must exceed max_n");
+        let mut factorials = Vec::with_capacity(max_n + 1);
+        factorials.push(1 % p);
+        for i in 1..=max_n as u64 {
+            factorials.push(mul_mod(factorials[i as usize - 1], i, p));
+        }
+        let mut inverse_factorials = vec![0; max_n + 1];
+        inverse_factorials[max_n] = inv_mod(factorials[max_n], p).expect("modulus must be a prime");
+        for i in (1..=max_n).rev() {
+            inverse_factorials[i - 1] = mul_mod(inverse_factorials[i], i as u64, p);
+        }
+        BinomialModTable {
+            p,
+            factorials,
+            inverse_factorials,
+        }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    /// Returns the largest supported `n`.
+    pub fn max_n(&self) -> usize {
+        self.factorials.len() - 1
+    }
+
+    /// Returns `C(n, k) mod p`, which is `0` for `k > n`.
+    ///
+    /// # Panics
+    /// Panics if `n` exceeds [`max_n`](Self::max_n).
+    pub fn binomial(&self, n: usize, k: usize) -> u64 {
+        let permutations = self.permutations(n, k);
+        mul_mod(permutations, self.inverse_factorials[k.min(n)], self.p)
+    }
+
+    /// Returns `n! / (n - k)! mod p`, the number of ordered selections of `k`
+    /// out of `n` items, which is `0` for `k > n`.
+    ///
+    /// # Panics
+    /// Panics if `n` exceeds [`max_n`](Self::max_n).
+    pub fn permutations(&self, n: usize, k: usize) -> u64 {
+        assert!(n <= self.max_n(), "n must not exceed max_n");
+        if k > n {
+            return 0;
+        }
+        mul_mod(self.factorials[n], self.inverse_factorials[n - k], self.p)
+    }
+}
+
+/// Returns `n! mod m` for every modulus `m` in `moduli`.
+///
+/// Together with enough pairwise coprime moduli this is a residue number
+/// system representation of `n!`, from which [`rns_to_biguint`] recovers the
+/// exact value as long as the product of the moduli exceeds `n!`. The
+/// residues are independent of each other, so the work is easily split up.
+///
+/// # Panics
+/// Panics if any modulus is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::{factorial_rns, rns_to_biguint};
+/// let moduli = [1_000_000_007, 1_000_000_009, 998_244_353];
+/// let residues = factorial_rns(20, &moduli);
+/// assert_eq!(residues, vec![146326063, 280522126, 401576539]);
+/// assert_eq!(rns_to_biguint(&residues, &moduli), 2432902008176640000u64.into());
+/// ```
+pub fn factorial_rns(n: u64, moduli: &[u64]) -> Vec<u64> {
+    moduli
+        .iter()
+        .map(|&m| {
+            assert!(m > 0, "moduli must be positive");
+            let mut acc = 1 % m;
+            for i in 2..=n {
+                if acc == 0 {
+                    break;
+                }
+                acc = mul_mod(acc, i % m, m);
+            }
+            acc
+        })
+        .collect()
+}
+
+/// Returns `(n - 1)! mod n`, or `None` if `n < 2` or `n` doesn't fit in a
+/// `u64`.
+///
+/// By Wilson's theorem this is `n - 1` exactly when `n` is prime. For
+/// composite `n` it is `0`, except for `n = 4` where `3! = 6 ≡ 2`.
+///
+/// # Examples
+/// ```
+/// use factorial::wilson_residue;
+/// assert_eq!(wilson_residue(&7u32), Some(6));
+/// assert_eq!(wilson_residue(&4u32), Some(2));
+/// assert_eq!(wilson_residue(&9u32), Some(0));
+/// ```
+pub fn wilson_residue<T: ToPrimitive + FromPrimitive>(n: &T) -> Option<T> {
+    let n = n.to_u64()?;
+    if n < 2 {
+        return None;
+    }
+    let mut acc = 1;
+    for i in 2..n {
+        acc = mul_mod(acc, i, n);
+        if acc == 0 {
+            break;
+        }
+    }
+    T::from_u64(acc)
+}
+
+/// Returns `[0! mod m, 1! mod m, ..., up_to! mod m]`.
+///
+/// Once a term is zero all later terms are zero as well, which happens from
+/// `n = S(m)` on, the smallest `n` with `m | n!`.
+///
+/// # Panics
+/// Panics if `m` is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_mod_sequence;
+/// assert_eq!(factorial_mod_sequence(10, 6), vec![1, 1, 2, 6, 4, 0, 0]);
+/// ```
+pub fn factorial_mod_sequence(m: u64, up_to: u64) -> Vec<u64> {
+    assert!(m > 0, "modulus must be positive");
+    let mut acc = 1 % m;
+    let mut sequence = Vec::with_capacity(up_to as usize + 1);
+    sequence.push(acc);
+    for i in 1..=up_to {
+        acc = mul_mod(acc, i % m, m);
+        sequence.push(acc);
+    }
+    sequence
+}
+
+/// Returns the hyperfactorial `H(n) = 1^1 * 2^2 * ... * n^n` modulo `m`.
+///
+/// Every term is raised by modular exponentiation, so this takes
+/// `O(n log n)` multiplications and never builds the huge exact value.
+///
+/// # Panics
+/// Panics if `m` is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::hyperfactorial_mod;
+/// // H(5) = 86400000
+/// assert_eq!(hyperfactorial_mod(5, 1_000_007), 86_400_000 % 1_000_007);
+/// ```
+pub fn hyperfactorial_mod(n: u64, m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+    let mut acc = 1 % m;
+    for i in 2..=n {
+        if acc == 0 {
+            break;
+        }
+        acc = mul_mod(acc, pow_mod(i, i, m), m);
+    }
+    acc
+}
+
+/// Returns the superfactorial `sf(n) = 1! * 2! * ... * n!` modulo `m`.
+///
+/// The factorials are reduced modulo `m` as they are built up, so this takes
+/// two multiplications per term. Once a factorial is divisible by `m` the
+/// result is `0`.
+///
+/// # Panics
+/// Panics if `m` is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::superfactorial_mod;
+/// // sf(4) = 1 * 2 * 6 * 24 = 288
+/// assert_eq!(superfactorial_mod(4, 100), 88);
+/// ```
+pub fn superfactorial_mod(n: u64, m: u64) -> u64 {
+    assert!(m > 0, "modulus must be positive");
+    let mut acc = 1 % m;
+    let mut factorial = 1 % m;
+    for i in 2..=n {
+        if acc == 0 {
+            break;
+        }
+        factorial = mul_mod(factorial, i % m, m);
+        acc = mul_mod(acc, factorial, m);
+    }
+    acc
+}
+
+/// Returns `n!` together with its residues modulo each of the `primes`.
+///
+/// Both are computed in the same pass over `1..=n`, but the residues never
+/// look at the exact value, so they can be used to cross-check it.
+///
+/// # Panics
+/// Panics if any of the primes is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_with_residues;
+/// let (value, residues) = factorial_with_residues(10, &[7, 11, 13]);
+/// assert_eq!(value, 3628800u32.into());
+/// assert_eq!(residues, vec![0, 10, 6]);
+/// ```
+pub fn factorial_with_residues(n: u64, primes: &[u64]) -> (BigUint, Vec<u64>) {
+    assert!(primes.iter().all(|&p| p > 0), "primes must be positive");
+    let mut value = BigUint::one();
+    let mut residues: Vec<u64> = primes.iter().map(|&p| 1 % p).collect();
+    for i in 2..=n {
+        value *= i;
+        for (r, &p) in residues.iter_mut().zip(primes) {
+            *r = mul_mod(*r, i % p, p);
+        }
+    }
+    (value, residues)
+}
+
+/// Returns the unique `x < m_1 * ... * m_k` with `x ≡ r_i (mod m_i)`, using
+/// the Chinese remainder theorem.
+///
+/// # Panics
+/// Panics if the slices differ in length or the moduli are not pairwise
+/// coprime.
+pub fn rns_to_biguint(residues: &[u64], moduli: &[u64]) -> BigUint {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "every residue needs a modulus"
+    );
+    // Garner's algorithm: add multiples of the product of the previous moduli
+    // until the next congruence is satisfied too.
+    let mut x = BigUint::zero();
+    let mut product = BigUint::one();
+    for (&r, &m) in residues.iter().zip(moduli) {
+        let x_mod = (&x % m).to_u64().unwrap();
+        let product_mod = (&product % m).to_u64().unwrap();
+        let inv = inv_mod(product_mod, m).expect("moduli must be pairwise coprime");
+        let r = r % m;
+        let diff = if r >= x_mod {
+            r - x_mod
+        } else {
+            m - (x_mod - r)
+        };
+        x += &product * mul_mod(diff, inv, m);
+        product *= m;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+
+    #[test]
+    fn hyperfactorial_mod_matches_exact() {
+        use crate::Hyperfactorial;
+        for n in 0..=30u64 {
+            let exact = BigUint::from(n).hyperfactorial();
+            for m in [1u64, 2, 10, 97, 1_000_000_007, u64::MAX] {
+                let expected = (&exact % m).to_u64().unwrap();
+                assert_eq!(
+                    hyperfactorial_mod(n, m),
+                    expected,
+                    "mismatch for n = {n}, m = {m}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn hyperfactorial_mod_zero_modulus() {
+        hyperfactorial_mod(3, 0);
+    }
+
+    #[test]
+    fn superfactorial_mod_matches_exact() {
+        use crate::Superfactorial;
+        for n in 0..=30u64 {
+            let exact = BigUint::from(n).superfactorial();
+            for m in [1u64, 2, 10, 97, 1_000_000_007, u64::MAX] {
+                let expected = (&exact % m).to_u64().unwrap();
+                assert_eq!(
+                    superfactorial_mod(n, m),
+                    expected,
+                    "mismatch for n = {n}, m = {m}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mersenne_matches_naive_reduction() {
+        for p in [2u32, 3, 5, 7, 13, 17, 19, 31, 61, 89] {
+            let modulus = (BigUint::one() << p) - 1u32;
+            for n in [0usize, 1, 2, 5, 10, 25, 100, 300] {
+                let expected = BigUint::from(n).factorial() % &modulus;
+                assert_eq!(
+                    factorial_mod_mersenne(n, p),
+                    expected,
+                    "mismatch for n = {n}, p = {p}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn binomial_table_matches_brute_force() {
+        use crate::{Binomial, FallingFactorial};
+        for p in [101u64, 1_000_000_007] {
+            let max_n = 100;
+            let table = BinomialModTable::new(max_n, p);
+            for n in 0..=max_n {
+                for k in 0..=n + 1 {
+                    let big_n = BigUint::from(n);
+                    let big_k = BigUint::from(k);
+                    assert_eq!(
+                        BigUint::from(table.binomial(n, k)),
+                        big_n.binomial(&big_k) % p,
+                        "C({n}, {k}) mod {p}"
+                    );
+                    assert_eq!(
+                        BigUint::from(table.permutations(n, k)),
+                        big_n.falling_factorial(&big_k) % p,
+                        "P({n}, {k}) mod {p}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n must not exceed max_n")]
+    fn binomial_table_rejects_large_n() {
+        BinomialModTable::new(10, 13).binomial(11, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must exceed max_n")]
+    fn binomial_table_rejects_small_modulus() {
+        BinomialModTable::new(10, 7);
+    }
+
+    #[test]
+    fn wilson_residue_cases() {
+        assert_eq!(wilson_residue(&0u32), None);
+        assert_eq!(wilson_residue(&1u32), None);
+        assert_eq!(wilson_residue(&2u32), Some(1));
+        assert_eq!(wilson_residue(&4u32), Some(2));
+        assert_eq!(wilson_residue(&1_000_003u64), Some(1_000_002));
+        assert_eq!(
+            wilson_residue(&BigUint::from(101u32)),
+            Some(BigUint::from(100u32))
+        );
+        for n in 5..500u64 {
+            let is_prime = (2..n).all(|d| n % d != 0);
+            let expected = if is_prime { n - 1 } else { 0 };
+            assert_eq!(wilson_residue(&n), Some(expected), "mismatch for {n}");
+        }
+    }
+
+    #[test]
+    fn mod_sequence_ten() {
+        let sequence = factorial_mod_sequence(10, 100);
+        assert_eq!(sequence.len(), 101);
+        assert_eq!(sequence[..5], [1, 1, 2, 6, 4]);
+        assert!(sequence[5..].iter().all(|&r| r == 0));
+    }
+
+    #[test]
+    fn mod_sequence_matches_naive() {
+        for m in [1u64, 2, 7, 12, 101, 1 << 40] {
+            let sequence = factorial_mod_sequence(m, 150);
+            for (n, &r) in sequence.iter().enumerate() {
+                assert_eq!(
+                    r,
+                    naive_factorial_mod(n as u64, m),
+                    "mismatch for n = {n}, m = {m}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn with_residues_matches_reduction() {
+        let primes = [2u64, 3, 101, 65_537, 1_000_000_007, (1u64 << 61) - 1];
+        for n in [0u64, 1, 2, 10, 100, 500] {
+            let (value, residues) = factorial_with_residues(n, &primes);
+            assert_eq!(value, BigUint::from(n).factorial());
+            for (&r, &p) in residues.iter().zip(&primes) {
+                assert_eq!(
+                    BigUint::from(r),
+                    &value % p,
+                    "mismatch for n = {n}, p = {p}"
+                );
+            }
+        }
+    }
+
+    fn naive_factorial_mod(n: u64, p: u64) -> u64 {
+        (1..=n).fold(1 % p, |acc, i| {
+            (u128::from(acc) * u128::from(i) % u128::from(p)) as u64
+        })
+    }
+
+    #[test]
+    fn montgomery_matches_naive() {
+        for p in [3u64, 5, 7, 11, 13, 101, 65_537] {
+            let ctx = MontgomeryFactorial::new(p);
+            for n in (0..300).chain([p - 1, p, p + 1]) {
+                assert_eq!(
+                    ctx.factorial_mod(n),
+                    naive_factorial_mod(n, p),
+                    "mismatch for n = {n}, p = {p}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn montgomery_large_modulus() {
+        for p in [998_244_353, 1_000_000_007, (1u64 << 61) - 1] {
+            let ctx = MontgomeryFactorial::new(p);
+            assert_eq!(ctx.modulus(), p);
+            for n in [0, 1, 2, 10, 1000, 10000] {
+                assert_eq!(ctx.factorial_mod(n), naive_factorial_mod(n, p));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be odd")]
+    fn montgomery_even_modulus() {
+        MontgomeryFactorial::new(10);
+    }
+
+    #[test]
+    fn rns_reconstructs_fifty_factorial() {
+        // Four primes just below 2^64, whose product exceeds 50! by far.
+        let moduli = [
+            18_446_744_073_709_551_557,
+            18_446_744_073_709_551_533,
+            18_446_744_073_709_551_521,
+            18_446_744_073_709_551_437,
+        ];
+        let expected = BigUint::from(50u32).factorial();
+        let residues = factorial_rns(50, &moduli);
+        for (&r, &m) in residues.iter().zip(&moduli) {
+            assert_eq!(BigUint::from(r), &expected % m);
+        }
+        assert_eq!(rns_to_biguint(&residues, &moduli), expected);
+    }
+
+    #[test]
+    fn rns_too_few_moduli() {
+        // The reconstruction is only correct modulo the product of the moduli.
+        let moduli = [1_000_000_007, 998_244_353];
+        let residues = factorial_rns(30, &moduli);
+        let product = BigUint::from(1_000_000_007u64 * 998_244_353);
+        assert_eq!(
+            rns_to_biguint(&residues, &moduli),
+            BigUint::from(30u32).factorial() % product
+        );
+    }
+
+    #[test]
+    fn rns_small_moduli() {
+        assert_eq!(factorial_rns(5, &[1, 7, 120, 121]), vec![0, 1, 0, 120]);
+    }
+
+    #[test]
+    #[should_panic(expected = "moduli must be pairwise coprime")]
+    fn rns_not_coprime() {
+        rns_to_biguint(&[1, 1], &[4, 6]);
+    }
+
+    #[test]
+    fn mersenne_modulus_one() {
+        assert_eq!(factorial_mod_mersenne(5, 1), BigUint::zero());
+    }
+
+    #[test]
+    fn mersenne_wilson() {
+        // 2^13 - 1 = 8191 is prime, so by Wilson's theorem 8190! = -1 mod 8191.
+        let modulus = BigUint::from(8191u32);
+        assert_eq!(factorial_mod_mersenne(8190, 13), modulus - 1u32);
+    }
+}
@@ -0,0 +1,556 @@
+//! Number theoretic properties of `n!` derived from its prime factorization.
+
+use crate::estimate::factorial_digit_count;
+use crate::{for_each_prime_power, prime_range, Factorial, TrailingZeros};
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive};
+use primal_sieve::Sieve;
+
+/// Returns the exponent of the prime `p` in `n!` using Legendre's formula
+/// `sum_{i >= 1} floor(n / p^i)`.
+pub(crate) fn legendre(n: u64, p: u64) -> u64 {
+    let mut exponent = 0;
+    let mut q = n;
+    while q >= p {
+        q /= p;
+        exponent += q;
+    }
+    exponent
+}
+
+/// Returns the prime factorization of `n!` as pairs of a prime and its
+/// exponent, in increasing order of the primes.
+///
+/// The sieve must be equal or greater than `n`.
+fn factorial_prime_exponents(n: usize, sieve: &Sieve) -> Vec<(usize, u64)> {
+    prime_range(sieve, 2, n)
+        .map(|p| (p, legendre(n as u64, p as u64)))
+        .collect()
+}
+
+fn divisor_count(exponents: &[(usize, u64)]) -> BigUint {
+    exponents
+        .iter()
+        .fold(BigUint::one(), |acc, &(_, e)| acc * (e + 1))
+}
+
+fn totient(exponents: &[(usize, u64)]) -> BigUint {
+    exponents.iter().fold(BigUint::one(), |acc, &(p, e)| {
+        acc * num_traits::pow(BigUint::from(p), e as usize - 1) * (p - 1)
+    })
+}
+
+/// Returns the exponent of every prime `p <= n` in `n!`, indexed by the
+/// position of the prime, i.e. the exponent of 2 comes first, then that of 3,
+/// 5, 7 and so on.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Panics
+/// Panics if an exponent doesn't fit in a `u32`. The exponent of 2 is the
+/// largest and is below `n`, so this can only happen for `n > u32::MAX`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_valuation_vector;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// // 10! = 2^8 * 3^4 * 5^2 * 7
+/// assert_eq!(factorial_valuation_vector(10, &sieve), vec![8, 4, 2, 1]);
+/// ```
+pub fn factorial_valuation_vector(n: usize, sieve: &Sieve) -> Vec<u32> {
+    factorial_prime_exponents(n, sieve)
+        .into_iter()
+        .map(|(_, e)| u32::try_from(e).expect("exponent does not fit in a u32"))
+        .collect()
+}
+
+/// Returns whether `n!` is squarefree, i.e. no prime occurs in it more than
+/// once.
+///
+/// This is only the case for `n <= 3`: from `4! = 2^3 * 3` on, the exponent
+/// of two is always larger than one. The answer is still derived from the
+/// Legendre exponents like the other functions in this family.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_is_squarefree;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// assert!(factorial_is_squarefree(3, &sieve));
+/// assert!(!factorial_is_squarefree(4, &sieve));
+/// ```
+pub fn factorial_is_squarefree(n: usize, sieve: &Sieve) -> bool {
+    // The exponent of two is the largest, so checking it suffices, but don't
+    // factor a huge n! just to find that out.
+    prime_range(sieve, 2, n.min(4)).all(|p| legendre(n as u64, p as u64) <= 1)
+}
+
+/// Returns `C(2p - 1, p - 1) mod p^e` for a prime `p`.
+///
+/// The binomial coefficient is `(p + 1) (p + 2) ... (2p - 1) / (p - 1)!`,
+/// and the denominator is coprime to `p`, so it is inverted with Euler's
+/// theorem.
+fn wolstenholme_residue(p: u64, e: u32) -> BigUint {
+    let modulus = num_traits::pow(BigUint::from(p), e as usize);
+    let mut numerator = BigUint::one();
+    let mut denominator = BigUint::one();
+    for i in 1..p {
+        numerator = numerator * (p + i) % &modulus;
+        denominator = denominator * i % &modulus;
+    }
+    // φ(p^e) = p^(e - 1) (p - 1), so the inverse is denominator^(φ - 1).
+    let phi = &modulus / p * (p - 1);
+    numerator * denominator.modpow(&(phi - 1u32), &modulus) % &modulus
+}
+
+/// Returns whether `p` is a Wolstenholme prime, i.e. a prime with
+/// `C(2p - 1, p - 1) ≡ 1 (mod p^4)`.
+///
+/// By Wolstenholme's theorem the congruence holds modulo `p^3` for every
+/// prime `p >= 5`; only two primes are known for which it also holds modulo
+/// `p^4`, 16843 and 2124679. This checks a single candidate in time linear in
+/// `p`, it is not meant to search for new ones.
+///
+/// # Examples
+/// ```
+/// use factorial::is_wolstenholme_prime;
+/// assert!(is_wolstenholme_prime(16843));
+/// assert!(!is_wolstenholme_prime(16829));
+/// ```
+pub fn is_wolstenholme_prime(p: u64) -> bool {
+    let is_prime = p >= 2
+        && (2..)
+            .take_while(|d| d * d <= p)
+            .all(|d| !p.is_multiple_of(d));
+    is_prime && wolstenholme_residue(p, 4).is_one()
+}
+
+/// Returns the number of divisors of `n!`.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_divisor_count;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// // 5! = 120 = 2^3 * 3 * 5
+/// assert_eq!(factorial_divisor_count(5, &sieve), 16u32.into());
+/// ```
+pub fn factorial_divisor_count(n: usize, sieve: &Sieve) -> BigUint {
+    divisor_count(&factorial_prime_exponents(n, sieve))
+}
+
+/// Returns Euler's totient of `n!`.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_totient;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// // φ(120) = 120 * (1 - 1/2) * (1 - 1/3) * (1 - 1/5)
+/// assert_eq!(factorial_totient(5, &sieve), 32u32.into());
+/// ```
+pub fn factorial_totient(n: usize, sieve: &Sieve) -> BigUint {
+    totient(&factorial_prime_exponents(n, sieve))
+}
+
+/// Returns the number of decimal digits, the number of divisors and Euler's
+/// totient of `n!`.
+///
+/// The prime factorization of `n!` is computed only once and shared by the
+/// divisor count and the totient, which is cheaper than calling
+/// [`factorial_divisor_count`] and [`factorial_totient`] separately.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_divisor_function_values;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// let (digits, divisors, totient) = factorial_divisor_function_values(5, &sieve);
+/// assert_eq!(digits, 3);
+/// assert_eq!(divisors, 16u32.into());
+/// assert_eq!(totient, 32u32.into());
+/// ```
+pub fn factorial_divisor_function_values(n: usize, sieve: &Sieve) -> (u64, BigUint, BigUint) {
+    let exponents = factorial_prime_exponents(n, sieve);
+    (
+        factorial_digit_count(n as u64),
+        divisor_count(&exponents),
+        totient(&exponents),
+    )
+}
+
+/// Returns the largest `m` such that `k^m` divides `n!`, together with the
+/// quotient `n! / k^m`.
+///
+/// The multiplicity follows from the prime factorization of `k` and
+/// Legendre's formula, so `n!` is never divided by `k` directly.
+///
+/// The sieve must be equal or greater than `n`.
+///
+/// # Panics
+/// If `k < 2`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_strip_factor;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(10);
+/// // 10! = 3628800 = 10^2 * 36288
+/// assert_eq!(factorial_strip_factor(10, 10, &sieve), (2, 36288u32.into()));
+/// ```
+pub fn factorial_strip_factor(n: usize, k: u64, sieve: &Sieve) -> (u64, BigUint) {
+    assert!(k >= 2, "cannot strip the factor {k}");
+    let mut exponents = factorial_prime_exponents(n, sieve);
+
+    // Factor k over the primes up to n; a prime factor above n doesn't divide
+    // n! at all.
+    let mut rest = k;
+    let mut k_exponents = Vec::new();
+    for (i, &(p, _)) in exponents.iter().enumerate() {
+        let p = p as u64;
+        let mut e = 0;
+        while rest.is_multiple_of(p) {
+            rest /= p;
+            e += 1;
+        }
+        if e > 0 {
+            k_exponents.push((i, e));
+        }
+        if rest == 1 {
+            break;
+        }
+    }
+    let m = if rest == 1 {
+        k_exponents
+            .iter()
+            .map(|&(i, e)| exponents[i].1 / e)
+            .min()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    for &(i, e) in &k_exponents {
+        exponents[i].1 -= m * e;
+    }
+    let quotient = exponents.iter().fold(BigUint::one(), |acc, &(p, e)| {
+        acc * num_traits::pow(BigUint::from(p), e as usize)
+    });
+    (m, quotient)
+}
+
+/// Returns the number of trailing zeros of `n!` written in the given base,
+/// i.e. the largest `m` such that `base^m` divides `n!`.
+///
+/// This is [`TrailingZeros::trailing_zeros_of_factorial_base`] for `u64`:
+/// the base is factored by trial division, and for every prime power `p^e`
+/// in it Legendre's formula gives how often `p^e` divides `n!`. In base ten
+/// this is the familiar count of factors five.
+///
+/// # Panics
+/// Panics if `base < 2`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_trailing_zeros_base;
+/// assert_eq!(factorial_trailing_zeros_base(10, 10), 2);
+/// // 10! = 0x375f00
+/// assert_eq!(factorial_trailing_zeros_base(10, 16), 2);
+/// ```
+pub fn factorial_trailing_zeros_base(n: u64, base: u64) -> u64 {
+    n.trailing_zeros_of_factorial_base(&base)
+}
+
+/// Returns the least `n >= 1` such that `m` divides `n!`, also known as the
+/// Kempner function `S(m)`.
+///
+/// For every prime power `p^e` dividing `m` the smallest multiple of `p`
+/// whose factorial contains `p` at least `e` times is found with Legendre's
+/// formula; the answer is the largest of these.
+///
+/// # Panics
+/// Panics if `m` is zero or does not fit in a `u64`.
+///
+/// # Examples
+/// ```
+/// use factorial::smallest_factorial_divisible_by;
+/// assert_eq!(smallest_factorial_divisible_by(&12u32), 4);
+/// assert_eq!(smallest_factorial_divisible_by(&25u32), 10);
+/// ```
+pub fn smallest_factorial_divisible_by<T: ToPrimitive>(m: &T) -> usize {
+    let m = m.to_u64().expect("m must fit in a u64");
+    assert!(m > 0, "no factorial is divisible by zero");
+    let mut result = 1;
+    for_each_prime_power(&m, |&p, e| {
+        // legendre(p * j, p) >= j, so j <= e multiples always suffice.
+        let (mut low, mut high) = (1, e);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if legendre(p * mid, p) >= e {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        result = result.max(p * low);
+    });
+    result as usize
+}
+
+/// Returns `gcd(a!, b!)`, which is simply `min(a, b)!` because the smaller
+/// factorial divides the larger one.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_gcd;
+/// assert_eq!(factorial_gcd(5, 8), 120u32.into());
+/// ```
+pub fn factorial_gcd(a: usize, b: usize) -> BigUint {
+    BigUint::from(a.min(b)).factorial()
+}
+
+/// Returns `lcm(a!, b!)`, which is simply `max(a, b)!` because the smaller
+/// factorial divides the larger one.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_lcm;
+/// assert_eq!(factorial_lcm(5, 8), 40320u32.into());
+/// ```
+pub fn factorial_lcm(a: usize, b: usize) -> BigUint {
+    BigUint::from(a.max(b)).factorial()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legendre_small() {
+        assert_eq!(legendre(10, 2), 8);
+        assert_eq!(legendre(25, 5), 6);
+        assert_eq!(legendre(4, 5), 0);
+        assert_eq!(legendre(0, 2), 0);
+    }
+
+    #[test]
+    fn divisor_function_values_twenty() {
+        let sieve = Sieve::new(20);
+        let (digits, divisors, totient) = factorial_divisor_function_values(20, &sieve);
+        assert_eq!(digits, factorial_digit_count(20));
+        assert_eq!(digits, 19);
+        assert_eq!(divisors, factorial_divisor_count(20, &sieve));
+        assert_eq!(divisors, 41040u32.into());
+        assert_eq!(totient, factorial_totient(20, &sieve));
+        assert_eq!(totient, 416_084_687_585_280_000u64.into());
+    }
+
+    #[test]
+    fn valuation_vector_reconstructs_factorial() {
+        let sieve = Sieve::new(500);
+        for n in [0usize, 1, 2, 10, 37, 100, 500] {
+            let exponents = factorial_valuation_vector(n, &sieve);
+            let product = sieve
+                .primes_from(2)
+                .zip(&exponents)
+                .fold(BigUint::one(), |acc, (p, &e)| {
+                    acc * num_traits::pow(BigUint::from(p), e as usize)
+                });
+            assert_eq!(
+                product,
+                BigUint::from(n).factorial(),
+                "mismatch for n = {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn divisor_count_brute_force() {
+        let sieve = Sieve::new(10);
+        for n in 0..=10usize {
+            let value = n.factorial();
+            let count = (1..=value).filter(|d| value % d == 0).count();
+            assert_eq!(factorial_divisor_count(n, &sieve), count.into());
+        }
+    }
+
+    #[test]
+    fn totient_brute_force() {
+        let sieve = Sieve::new(8);
+        for n in 0..=8u64 {
+            let value = n.factorial();
+            let phi = (1..=value).filter(|&k| gcd(k, value) == 1).count();
+            assert_eq!(factorial_totient(n as usize, &sieve), phi.into());
+        }
+    }
+
+    #[test]
+    fn squarefree_small() {
+        let sieve = Sieve::new(100);
+        assert!(factorial_is_squarefree(0, &sieve));
+        assert!(factorial_is_squarefree(1, &sieve));
+        assert!(factorial_is_squarefree(2, &sieve));
+        assert!(factorial_is_squarefree(3, &sieve));
+        for n in 4..=100 {
+            assert!(
+                !factorial_is_squarefree(n, &sieve),
+                "{n}! is not squarefree"
+            );
+            assert!(factorial_valuation_vector(n, &sieve).iter().any(|&e| e > 1));
+        }
+    }
+
+    #[test]
+    fn wolstenholme_theorem() {
+        let sieve = Sieve::new(1000);
+        for p in sieve.primes_from(5).take_while(|&p| p < 1000) {
+            let p = p as u64;
+            assert!(wolstenholme_residue(p, 3).is_one(), "fails for p = {p}");
+            assert!(!is_wolstenholme_prime(p), "{p} is no Wolstenholme prime");
+        }
+    }
+
+    #[test]
+    fn wolstenholme_residue_matches_binomial() {
+        use crate::Binomial;
+        for p in [2u64, 3, 5, 7, 11, 13] {
+            let binomial = BigUint::from(2 * p - 1).binomial(&BigUint::from(p - 1));
+            let modulus = num_traits::pow(BigUint::from(p), 4);
+            assert_eq!(wolstenholme_residue(p, 4), binomial % modulus);
+        }
+    }
+
+    #[test]
+    fn wolstenholme_primes() {
+        assert!(is_wolstenholme_prime(16843));
+        for n in [0u64, 1, 2, 3, 4, 9, 16841, 16845] {
+            assert!(!is_wolstenholme_prime(n), "{n} is no Wolstenholme prime");
+        }
+    }
+
+    #[test]
+    fn strip_factor_ten() {
+        let sieve = Sieve::new(10);
+        assert_eq!(factorial_strip_factor(10, 10, &sieve), (2, 36288u32.into()));
+    }
+
+    #[test]
+    fn strip_factor_brute_force() {
+        let sieve = Sieve::new(30);
+        for n in 0..=30usize {
+            for k in 2..=50u64 {
+                let mut quotient = BigUint::from(n).factorial();
+                let mut m = 0;
+                while &quotient % k == BigUint::from(0u32) {
+                    quotient /= k;
+                    m += 1;
+                }
+                assert_eq!(
+                    factorial_strip_factor(n, k, &sieve),
+                    (m, quotient),
+                    "mismatch for n = {n}, k = {k}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn strip_factor_one_panics() {
+        factorial_strip_factor(10, 1, &Sieve::new(10));
+    }
+
+    #[test]
+    fn trailing_zeros_base_brute_force() {
+        for base in 2..=40u64 {
+            let mut value = BigUint::one();
+            for n in 0..=40u64 {
+                if n > 0 {
+                    value *= n;
+                }
+                let mut zeros = 0;
+                let mut rest = value.clone();
+                while &rest % base == BigUint::from(0u32) {
+                    rest /= base;
+                    zeros += 1;
+                }
+                assert_eq!(
+                    factorial_trailing_zeros_base(n, base),
+                    zeros,
+                    "mismatch for n = {n}, base = {base}"
+                );
+            }
+        }
+        // 20! = 2^18 * 3^8 * ..., and 12 = 2^2 * 3
+        assert_eq!(factorial_trailing_zeros_base(20, 12), 8);
+    }
+
+    #[test]
+    fn trailing_zeros_base_ten() {
+        for n in [0u64, 4, 5, 25, 100, 1_000_000] {
+            assert_eq!(factorial_trailing_zeros_base(n, 10), legendre(n, 5));
+            assert_eq!(
+                factorial_trailing_zeros_base(n, 10),
+                crate::DeferredFactorial::new(n).trailing_zeros()
+            );
+        }
+    }
+
+    #[test]
+    fn smallest_factorial_divisible_by_small() {
+        assert_eq!(smallest_factorial_divisible_by(&12u32), 4);
+        assert_eq!(smallest_factorial_divisible_by(&25u32), 10);
+        assert_eq!(smallest_factorial_divisible_by(&1u32), 1);
+        assert_eq!(smallest_factorial_divisible_by(&BigUint::from(97u32)), 97);
+    }
+
+    #[test]
+    fn smallest_factorial_divisible_by_brute_force() {
+        for m in 1..=2000u64 {
+            let mut n = 1;
+            let mut value = BigUint::one();
+            while &value % m != BigUint::from(0u32) {
+                n += 1;
+                value *= n;
+            }
+            assert_eq!(
+                smallest_factorial_divisible_by(&m),
+                n,
+                "mismatch for m = {m}"
+            );
+        }
+    }
+
+    #[test]
+    fn factorial_gcd_lcm_brute_force() {
+        for a in 0..=12usize {
+            for b in 0..=12usize {
+                let (fa, fb) = (a.factorial() as u64, b.factorial() as u64);
+                let g = gcd(fa, fb);
+                assert_eq!(factorial_gcd(a, b), g.into(), "gcd mismatch for {a}, {b}");
+                assert_eq!(
+                    factorial_lcm(a, b),
+                    (fa / g * fb).into(),
+                    "lcm mismatch for {a}, {b}"
+                );
+            }
+        }
+        assert_eq!(factorial_gcd(5, 8), 120u32.into());
+    }
+
+    fn gcd(mut a: u64, mut b: u64) -> u64 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
@@ -0,0 +1,349 @@
+//! Products over ranges of integers.
+
+use crate::Factorial;
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedMul, FromPrimitive, One, Zero};
+use std::ops::Mul;
+
+/// Returns the product `term(low + 1) * term(low + 2) * ... * term(high)`,
+/// which is `1` if the range is empty, or `None` if a partial product
+/// overflows.
+///
+/// The range is split in halves recursively so the operands of every
+/// multiplication are of similar size, which is much faster than multiplying
+/// the terms one at a time.
+pub(crate) fn product_tree<T, F>(low: usize, high: usize, term: &F) -> Option<T>
+where
+    T: CheckedMul + One,
+    F: Fn(usize) -> T,
+{
+    if high <= low {
+        return Some(T::one());
+    }
+    if high - low <= 16 {
+        let mut acc = term(low + 1);
+        for i in low + 2..=high {
+            acc = acc.checked_mul(&term(i))?;
+        }
+        return Some(acc);
+    }
+    let mid = low + (high - low) / 2;
+    product_tree(low, mid, term)?.checked_mul(&product_tree(mid, high, term)?)
+}
+
+/// Returns the product `(low + 1) * (low + 2) * ... * high`, which is `1` if
+/// the range is empty.
+pub(crate) fn range_product(low: usize, high: usize) -> BigUint {
+    product_tree(low, high, &BigUint::from).expect("BigUint does not overflow")
+}
+
+/// A number in the log semiring, where "multiplying" adds the logarithms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LogValue(f64);
+
+impl Mul for LogValue {
+    type Output = LogValue;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: LogValue) -> LogValue {
+        LogValue(self.0 + rhs.0)
+    }
+}
+
+impl One for LogValue {
+    fn one() -> LogValue {
+        LogValue(0.0)
+    }
+}
+
+impl CheckedMul for LogValue {
+    fn checked_mul(&self, rhs: &LogValue) -> Option<LogValue> {
+        Some(*self * *rhs)
+    }
+}
+
+/// Returns `ln(n!)` by running the factorial's product tree in the log
+/// semiring, i.e. summing `ln(i)` with the same balanced splitting.
+///
+/// The pairwise summation keeps the rounding error small, so this is a
+/// stable way to get `ln(n!)` for small and moderate `n`, though it costs
+/// `n` logarithms.
+///
+/// # Examples
+/// ```
+/// use factorial::log_semiring_factorial;
+/// assert!((log_semiring_factorial(5) - 120f64.ln()).abs() < 1e-14);
+/// ```
+pub fn log_semiring_factorial(n: usize) -> f64 {
+    let LogValue(ln) = product_tree(0, n, &|i| LogValue((i as f64).ln()))
+        .expect("the log semiring never overflows");
+    ln
+}
+
+/// Returns the product `term(1) * term(2) * ... * term(n)`, if no partial
+/// product overflows the type `T`.
+///
+/// The terms are multiplied with `product_tree`, which splits the range in
+/// halves so the operands stay of similar size, so any "factorial-like"
+/// product can be computed efficiently. As long as all terms are non-zero, a
+/// partial product overflows exactly when the full product does.
+///
+/// # Examples
+/// ```
+/// use factorial::generalized_factorial;
+/// // The product of the first four odd numbers, 1 * 3 * 5 * 7
+/// assert_eq!(generalized_factorial(4, |i| 2 * i as u32 - 1), Some(105));
+/// assert_eq!(generalized_factorial(30, |i| i as u32), None);
+/// ```
+pub fn generalized_factorial<T, F>(n: usize, term: F) -> Option<T>
+where
+    T: CheckedMul + One,
+    F: Fn(usize) -> T,
+{
+    product_tree(0, n, &term)
+}
+
+/// Returns the product of the arithmetic progression
+/// `a * (a + d) * (a + 2d) * ... * (a + (k - 1)d)`, if it doesn't overflow
+/// the type `T`.
+///
+/// This generalizes the rising factorial (`d = 1`) and the double factorial
+/// (`a = 1` or `a = 2`, `d = 2`). The terms are collected first and then
+/// multiplied pairwise by `product_tree`, the helper behind `range_product`.
+/// The empty product for `k = 0` is `1`.
+///
+/// # Examples
+/// ```
+/// use factorial::arithmetic_factorial;
+/// // 1 * 3 * 5 * 7
+/// assert_eq!(arithmetic_factorial(1u32, 2, 4), Some(105));
+/// // 5 * 6 * 7, the rising factorial
+/// assert_eq!(arithmetic_factorial(5u32, 1, 3), Some(210));
+/// ```
+pub fn arithmetic_factorial<T>(a: T, d: T, k: usize) -> Option<T>
+where
+    T: CheckedAdd + CheckedMul + Zero + One + Clone,
+{
+    if k > 0 && a.is_zero() {
+        // Later terms might not even fit, but the product is zero anyway.
+        return Some(T::zero());
+    }
+    let mut terms = Vec::with_capacity(k);
+    let mut term = a;
+    for _ in 0..k {
+        let next = term.checked_add(&d);
+        terms.push(term);
+        match next {
+            Some(next) => term = next,
+            // Only fine if this was the last term.
+            None if terms.len() == k => break,
+            None => return None,
+        }
+    }
+    product_tree(0, k, &|i| terms[i - 1].clone())
+}
+
+/// Returns the product of all `i` in `1..=n` with
+/// `i ≡ residue (mod modulus)`, if it doesn't overflow the type `T`.
+///
+/// This generalizes the multifactorial, which is the residue class of `n`
+/// itself, to any residue class. The members of the class are multiplied
+/// with `product_tree`. The empty product is `1`.
+///
+/// # Panics
+/// Panics if `modulus` is zero.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_by_residue;
+/// // 3 * 6 * 9
+/// assert_eq!(factorial_by_residue::<u32>(9, 3, 0), Some(162));
+/// // 2 * 5 * 8
+/// assert_eq!(factorial_by_residue::<u32>(9, 3, 5), Some(80));
+/// ```
+pub fn factorial_by_residue<T>(n: usize, modulus: usize, residue: usize) -> Option<T>
+where
+    T: CheckedMul + One + FromPrimitive,
+{
+    assert!(modulus > 0, "modulus must be positive");
+    let first = match residue % modulus {
+        0 => modulus,
+        r => r,
+    };
+    if first > n {
+        return Some(T::one());
+    }
+    let count = (n - first) / modulus + 1;
+    // The last term is the largest, so if it fits, they all do.
+    T::from_usize(first + (count - 1) * modulus)?;
+    product_tree(0, count, &|i| {
+        T::from_usize(first + (i - 1) * modulus).expect("smaller than the last term")
+    })
+}
+
+/// Returns `(pivot!, (pivot + 1) * ... * n)`, two halves whose product is
+/// `n!`.
+///
+/// The halves can be computed, stored and combined independently, e.g. to
+/// spread the computation of a huge factorial over several machines.
+///
+/// # Panics
+/// Panics if `pivot > n`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_split;
+/// let (low, high) = factorial_split(6, 3);
+/// assert_eq!(low, 6u32.into());
+/// assert_eq!(high, 120u32.into());
+/// ```
+pub fn factorial_split(n: usize, pivot: usize) -> (BigUint, BigUint) {
+    assert!(pivot <= n, "pivot must not exceed n");
+    (BigUint::from(pivot).factorial(), range_product(pivot, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_product_small() {
+        assert_eq!(range_product(0, 0), BigUint::one());
+        assert_eq!(range_product(5, 3), BigUint::one());
+        assert_eq!(range_product(0, 5), BigUint::from(120u32));
+        assert_eq!(range_product(3, 5), BigUint::from(20u32));
+    }
+
+    #[test]
+    fn generalized_squares() {
+        for n in [0usize, 1, 5, 20, 100, 333] {
+            let expected = num_traits::pow(BigUint::from(n).factorial(), 2);
+            let squares = generalized_factorial(n, |i| BigUint::from(i * i));
+            assert_eq!(squares, Some(expected), "mismatch for n = {n}");
+        }
+    }
+
+    #[test]
+    fn generalized_overflow() {
+        assert_eq!(
+            generalized_factorial(10, |i| (i * i) as u64),
+            Some(3_628_800 * 3_628_800)
+        );
+        assert_eq!(generalized_factorial(11, |i| (i * i) as u32), None);
+    }
+
+    #[test]
+    fn arithmetic_odd_numbers() {
+        assert_eq!(arithmetic_factorial(1u32, 2, 4), Some(105));
+        assert_eq!(arithmetic_factorial(7u32, 3, 0), Some(1));
+        assert_eq!(arithmetic_factorial(0u32, 3, 5), Some(0));
+        assert_eq!(arithmetic_factorial(4u32, 0, 3), Some(64));
+    }
+
+    #[test]
+    fn arithmetic_matches_factorials() {
+        use crate::DoubleFactorial;
+        for k in 0..=16usize {
+            // rising factorial starting at one
+            assert_eq!(
+                arithmetic_factorial(1u64, 1, k),
+                Some((k as u64).factorial())
+            );
+            assert_eq!(
+                arithmetic_factorial(2u64, 2, k),
+                Some((2 * k as u64).double_factorial())
+            );
+        }
+        let big = arithmetic_factorial(BigUint::one(), BigUint::one(), 300);
+        assert_eq!(big, Some(BigUint::from(300u32).factorial()));
+    }
+
+    #[test]
+    fn arithmetic_overflow() {
+        assert_eq!(arithmetic_factorial(1u8, 2, 4), Some(105));
+        assert_eq!(arithmetic_factorial(1u8, 2, 5), None);
+        assert_eq!(arithmetic_factorial(1u8, 254, 2), Some(255));
+        assert_eq!(arithmetic_factorial(2u8, 254, 2), None);
+    }
+
+    #[test]
+    fn by_residue_small() {
+        assert_eq!(factorial_by_residue::<u32>(9, 3, 0), Some(3 * 6 * 9));
+        assert_eq!(factorial_by_residue::<u32>(9, 3, 1), Some(28));
+        assert_eq!(factorial_by_residue::<u32>(9, 3, 7), Some(28));
+        assert_eq!(factorial_by_residue::<u32>(1, 3, 2), Some(1));
+        assert_eq!(factorial_by_residue::<u32>(0, 1, 0), Some(1));
+        assert_eq!(factorial_by_residue::<u8>(300, 1, 0), None);
+    }
+
+    #[test]
+    fn by_residue_matches_factorials() {
+        use crate::Multifactorial;
+        for n in 1..=30usize {
+            assert_eq!(
+                factorial_by_residue(n, 1, 0),
+                Some(BigUint::from(n).factorial())
+            );
+            for k in 1..=5 {
+                assert_eq!(
+                    factorial_by_residue(n, k, n),
+                    Some(BigUint::from(n).multifactorial(k))
+                );
+            }
+        }
+        // The residue classes together make up the factorial.
+        let classes = (0..7).map(|r| factorial_by_residue::<BigUint>(100, 7, r).unwrap());
+        assert_eq!(
+            classes.product::<BigUint>(),
+            BigUint::from(100u32).factorial()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn by_residue_zero_modulus() {
+        factorial_by_residue::<u32>(3, 0, 0);
+    }
+
+    #[test]
+    fn log_semiring_matches_log_factorial() {
+        use crate::estimate::ln_factorial;
+        assert_eq!(log_semiring_factorial(0), 0.0);
+        assert_eq!(log_semiring_factorial(1), 0.0);
+        for n in (2..200).chain([1000, 10_000, 100_000]) {
+            let expected = ln_factorial(n as u64);
+            let err = ((log_semiring_factorial(n) - expected) / expected).abs();
+            assert!(err < 1e-14, "n = {n}: relative error {err}");
+        }
+    }
+
+    #[test]
+    fn split_halves_multiply_to_factorial() {
+        for n in [0usize, 1, 10, 35, 100, 500, 1234] {
+            let expected = BigUint::from(n).factorial();
+            for pivot in [0, n / 3, n / 2, n] {
+                let (low, high) = factorial_split(n, pivot);
+                assert_eq!(
+                    low * high,
+                    expected,
+                    "mismatch for n = {n}, pivot = {pivot}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "pivot must not exceed n")]
+    fn split_pivot_too_large() {
+        factorial_split(3, 4);
+    }
+
+    #[test]
+    fn range_product_matches_naive() {
+        let mut expected = BigUint::one();
+        for high in 1..300 {
+            expected *= high;
+            assert_eq!(range_product(0, high), expected, "mismatch for {high}");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Factorial computations that can be stopped and resumed.
+
+use crate::product::range_product;
+use num_bigint::BigUint;
+use num_traits::One;
+
+/// A factorial computation in progress, holding `partial = n_done!`.
+///
+/// With the `serde` feature the progress can be serialized, so a long
+/// computation can be saved periodically and survive a restart of the
+/// process.
+///
+/// # Examples
+/// ```
+/// use factorial::{Factorial, FactorialProgress};
+/// let progress = FactorialProgress::new().resume(50);
+/// // ... save and restore `progress` ...
+/// let value = progress.resume(100).finish();
+/// assert_eq!(value, num_bigint::BigUint::from(100u32).factorial());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FactorialProgress {
+    /// The argument the factorial has been computed up to.
+    pub n_done: usize,
+    /// The value `n_done!`.
+    pub partial: BigUint,
+}
+
+impl FactorialProgress {
+    /// Starts at `0! = 1`.
+    pub fn new() -> Self {
+        FactorialProgress {
+            n_done: 0,
+            partial: BigUint::one(),
+        }
+    }
+
+    /// Advances the computation to `target_n!`, multiplying by the product
+    /// `(n_done + 1) * ... * target_n`.
+    ///
+    /// The progress is returned unchanged if `target_n` is not larger than
+    /// `n_done`.
+    pub fn resume(mut self, target_n: usize) -> Self {
+        if target_n > self.n_done {
+            self.partial *= range_product(self.n_done, target_n);
+            self.n_done = target_n;
+        }
+        self
+    }
+
+    /// Returns the computed value `n_done!`.
+    pub fn finish(self) -> BigUint {
+        self.partial
+    }
+}
+
+impl Default for FactorialProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+
+    #[test]
+    fn resume_in_steps() {
+        let mut progress = FactorialProgress::default();
+        for n in (0..=300).step_by(37) {
+            progress = progress.resume(n);
+            assert_eq!(progress.n_done, n);
+            assert_eq!(progress.partial, BigUint::from(n).factorial());
+        }
+        let done = progress.clone().resume(10);
+        assert_eq!(done, progress);
+        assert_eq!(FactorialProgress::new().finish(), BigUint::one());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let saved = serde_json::to_string(&FactorialProgress::new().resume(500)).unwrap();
+        let restored: FactorialProgress = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restored.n_done, 500);
+        assert_eq!(
+            restored.resume(1000).finish(),
+            BigUint::from(1000u32).factorial()
+        );
+    }
+}
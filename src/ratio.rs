@@ -0,0 +1,65 @@
+//! Exact ratios of factorials.
+
+use crate::product::range_product;
+use num_bigint::BigUint;
+use num_rational::BigRational;
+
+/// Returns the exact fraction `a! / b!`.
+///
+/// This is an integer when `a >= b` and a proper fraction otherwise. Only the
+/// terms in which the two factorials differ are multiplied.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_ratio_rational;
+/// use num_rational::BigRational;
+/// assert_eq!(
+///     factorial_ratio_rational(3, 5),
+///     BigRational::new(1.into(), 20.into())
+/// );
+/// assert_eq!(factorial_ratio_rational(5, 3), BigRational::from_integer(20.into()));
+/// ```
+pub fn factorial_ratio_rational(a: usize, b: usize) -> BigRational {
+    let one = BigUint::from(1u8);
+    if a >= b {
+        BigRational::new(range_product(b, a).into(), one.into())
+    } else {
+        BigRational::new(one.into(), range_product(a, b).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn ratio_proper_fraction() {
+        assert_eq!(
+            factorial_ratio_rational(3, 5),
+            BigRational::new(1.into(), 20.into())
+        );
+    }
+
+    #[test]
+    fn ratio_equal_arguments() {
+        assert_eq!(
+            factorial_ratio_rational(7, 7),
+            BigRational::from_integer(1.into())
+        );
+    }
+
+    #[test]
+    fn ratio_matches_factorials() {
+        for a in 0..40usize {
+            for b in 0..40usize {
+                let expected = BigRational::new(
+                    BigInt::from(BigUint::from(a).factorial()),
+                    BigInt::from(BigUint::from(b).factorial()),
+                );
+                assert_eq!(factorial_ratio_rational(a, b), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Recreational properties involving factorials.
+
+use crate::array;
+
+/// Returns whether `n` is a factorion, i.e. equal to the sum of the
+/// factorials of its decimal digits.
+///
+/// The only factorions are 1, 2, 145 and 40585.
+///
+/// # Examples
+/// ```
+/// use factorial::is_factorion;
+/// // 1! + 4! + 5! = 1 + 24 + 120
+/// assert!(is_factorion(145));
+/// assert!(!is_factorion(146));
+/// ```
+pub fn is_factorion(n: u64) -> bool {
+    let mut sum = 0u64;
+    let mut rest = n;
+    loop {
+        sum += array::SMALL_FACTORIAL[(rest % 10) as usize] as u64;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    sum == n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_factorions() {
+        assert!(is_factorion(145));
+        assert!(is_factorion(40585));
+        assert!(!is_factorion(146));
+        assert!(!is_factorion(0));
+        assert!(!is_factorion(u64::MAX));
+    }
+
+    #[test]
+    fn factorions_brute_force() {
+        let found: Vec<u64> = (0..100_000).filter(|&n| is_factorion(n)).collect();
+        assert_eq!(found, vec![1, 2, 145, 40585]);
+    }
+}
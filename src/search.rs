@@ -0,0 +1,150 @@
+//! Searching for factorials by their value.
+
+use num_bigint::BigUint;
+use num_traits::{One, Unsigned};
+
+/// Returns all `(n, n!)` with `low <= n! <= high`, in increasing order of
+/// `n`.
+///
+/// The factorials are computed incrementally until they exceed `high`. Note
+/// that both `0!` and `1!` are `1`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorials_in_range;
+/// let found = factorials_in_range(&100u32.into(), &10000u32.into());
+/// let arguments: Vec<usize> = found.iter().map(|(n, _)| *n).collect();
+/// assert_eq!(arguments, vec![5, 6, 7]);
+/// ```
+pub fn factorials_in_range(low: &BigUint, high: &BigUint) -> Vec<(usize, BigUint)> {
+    let mut found = Vec::new();
+    let mut n = 0;
+    let mut value = BigUint::one();
+    while &value <= high {
+        if &value >= low {
+            found.push((n, value.clone()));
+        }
+        n += 1;
+        value *= n;
+    }
+    found
+}
+
+/// Returns the number of arguments `n >= 0` with `n! < x`.
+///
+/// This is the largest such `n` plus one, as `0!` is counted as well, or zero
+/// if `x <= 1`. The factorials are computed incrementally until they reach
+/// `x`.
+///
+/// # Examples
+/// ```
+/// use factorial::count_factorials_below;
+/// // 0!, 1!, ..., 6! = 720 are below 1000, 7! = 5040 is not.
+/// assert_eq!(count_factorials_below(&1000u32.into()), 7);
+/// ```
+pub fn count_factorials_below(x: &BigUint) -> usize {
+    let mut n = 0;
+    let mut value = BigUint::one();
+    while &value < x {
+        n += 1;
+        value *= n;
+    }
+    n
+}
+
+/// Returns whether `a! == b!` without computing either factorial.
+///
+/// The factorial is injective except for `0! == 1! == 1`, so this holds
+/// exactly when `a == b` or `{a, b} == {0, 1}`.
+///
+/// # Examples
+/// ```
+/// use factorial::factorials_equal;
+/// assert!(factorials_equal(&0u32, &1u32));
+/// assert!(factorials_equal(&7u32, &7u32));
+/// assert!(!factorials_equal(&1u32, &2u32));
+/// ```
+pub fn factorials_equal<T: Unsigned>(a: &T, b: &T) -> bool {
+    a == b || (a.is_zero() && b.is_one()) || (a.is_one() && b.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate::factorial_digit_count;
+
+    #[test]
+    fn count_below_small() {
+        assert_eq!(count_factorials_below(&BigUint::from(1000u32)), 7);
+        assert_eq!(count_factorials_below(&BigUint::from(720u32)), 6);
+        assert_eq!(count_factorials_below(&BigUint::from(721u32)), 7);
+        assert_eq!(count_factorials_below(&BigUint::from(2u32)), 2);
+        assert_eq!(count_factorials_below(&BigUint::one()), 0);
+        assert_eq!(count_factorials_below(&BigUint::from(0u32)), 0);
+    }
+
+    #[test]
+    fn count_below_matches_range() {
+        let x = num_traits::pow(BigUint::from(10u32), 300);
+        let found = factorials_in_range(&BigUint::from(0u32), &(&x - 1u32));
+        assert_eq!(count_factorials_below(&x), found.len());
+    }
+
+    #[test]
+    fn equal_zero_and_one() {
+        assert!(factorials_equal(&0u8, &1u8));
+        assert!(factorials_equal(&1u8, &0u8));
+        assert!(factorials_equal(&BigUint::from(0u32), &BigUint::one()));
+    }
+
+    #[test]
+    fn equal_matches_values() {
+        use crate::Factorial;
+        for a in 0..=20u64 {
+            for b in 0..=20u64 {
+                assert_eq!(
+                    factorials_equal(&a, &b),
+                    a.factorial() == b.factorial(),
+                    "mismatch for {a}, {b}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn range_small_window() {
+        let found = factorials_in_range(&BigUint::from(100u32), &BigUint::from(10000u32));
+        assert_eq!(
+            found,
+            vec![
+                (5, BigUint::from(120u32)),
+                (6, BigUint::from(720u32)),
+                (7, BigUint::from(5040u32))
+            ]
+        );
+    }
+
+    #[test]
+    fn range_includes_bounds() {
+        let found = factorials_in_range(&BigUint::from(1u32), &BigUint::from(6u32));
+        let arguments: Vec<usize> = found.iter().map(|(n, _)| *n).collect();
+        assert_eq!(arguments, vec![0, 1, 2, 3]);
+        assert!(factorials_in_range(&BigUint::from(7u32), &BigUint::from(23u32)).is_empty());
+        assert!(factorials_in_range(&BigUint::from(10u32), &BigUint::from(1u32)).is_empty());
+    }
+
+    #[test]
+    fn range_by_digits() {
+        // All factorials with between 100 and 200 digits
+        let low = num_traits::pow(BigUint::from(10u32), 99);
+        let high = num_traits::pow(BigUint::from(10u32), 200) - 1u32;
+        let found = factorials_in_range(&low, &high);
+        assert_eq!(found.first().unwrap().0, 70);
+        assert_eq!(found.last().unwrap().0, 120);
+        for (n, value) in found {
+            let digits = value.to_string().len() as u64;
+            assert_eq!(digits, factorial_digit_count(n as u64));
+            assert!((100..=200).contains(&digits));
+        }
+    }
+}
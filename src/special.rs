@@ -0,0 +1,202 @@
+//! Special factorial-like functions.
+
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedMul, Unsigned};
+
+/// Returns the subfactorial `!n`, the number of derangements of `n`
+/// elements, or `None` on overflow.
+///
+/// Uses the recurrence `!n = (n - 1) (!(n - 1) + !(n - 2))` starting from
+/// `!0 = 1` and `!1 = 0`, which only involves non-negative terms.
+pub(crate) fn checked_subfactorial<T>(n: &T) -> Option<T>
+where
+    T: PartialOrd + Unsigned + CheckedMul + CheckedAdd + Clone,
+{
+    if n.is_zero() {
+        return Some(T::one());
+    }
+    let (mut prev, mut cur) = (T::one(), T::zero());
+    let mut i = T::one();
+    while &i < n {
+        let next = i.checked_mul(&cur.checked_add(&prev)?)?;
+        i = i + T::one();
+        prev = cur;
+        cur = next;
+    }
+    Some(cur)
+}
+
+/// Returns `H(n) / (n^(n^2/2 + n/2 + 1/12) e^(-n^2/4))`, where `H(n)` is the
+/// hyperfactorial `1^1 2^2 ... n^n`.
+///
+/// As `n` grows this converges to the Glaisher–Kinkelin constant
+/// `A ≈ 1.2824271291`, with an error of roughly `1 / (720 n^2)`. The ratio is
+/// computed in log-space, so it does not overflow even though `H(n)` itself
+/// does for very small `n`. Beyond a few thousand the rounding error of the
+/// logarithmic sum dominates and the ratio no longer gets closer to `A`.
+///
+/// Returns `NaN` for `n == 0`.
+///
+/// # Examples
+/// ```
+/// use factorial::hyperfactorial_ratio;
+/// assert!((hyperfactorial_ratio(1000) - 1.2824271291).abs() < 1e-6);
+/// ```
+pub fn hyperfactorial_ratio(n: u64) -> f64 {
+    if n == 0 {
+        return f64::NAN;
+    }
+    let ln_hyperfactorial: f64 = (2..=n).map(|i| i as f64 * (i as f64).ln()).sum();
+    let n = n as f64;
+    let exponent = n * n / 2.0 + n / 2.0 + 1.0 / 12.0;
+    (ln_hyperfactorial - exponent * n.ln() + n * n / 4.0).exp()
+}
+
+/// Returns the number of decimal digits of the hyperfactorial
+/// `H(n) = 1^1 2^2 ... n^n`.
+///
+/// The digit count follows from `log10 H(n) = sum_i i log10(i)`, which
+/// avoids computing `H(n)` at all. The sum is accurate to many digits for
+/// any practical `n`, so the count is only off if `log10 H(n)` falls within
+/// that rounding error of an integer.
+///
+/// # Examples
+/// ```
+/// use factorial::hyperfactorial_digit_count;
+/// // H(5) = 86400000
+/// assert_eq!(hyperfactorial_digit_count(5), 8);
+/// ```
+pub fn hyperfactorial_digit_count(n: u64) -> u64 {
+    let log10: f64 = (2..=n).map(|i| i as f64 * (i as f64).log10()).sum();
+    log10.floor() as u64 + 1
+}
+
+/// The exponential factorials `0$` to `4$`.
+const SMALL_EXPONENTIAL_FACTORIAL: [u32; 5] = [1, 1, 2, 9, 262_144];
+
+/// Returns the exponential factorial `n$ = n^((n - 1)^(...^(2^1)))`, or
+/// `None` for `n > 4`.
+///
+/// The power tower grows so fast that `5$` already has 183231 digits and
+/// `6$` could not be stored at all, so only the first few values are
+/// computed. Use [`exponential_factorial_digit_count`] to find out how large
+/// the others are.
+///
+/// # Examples
+/// ```
+/// use factorial::exponential_factorial;
+/// assert_eq!(exponential_factorial(4), Some(262144u32.into()));
+/// assert_eq!(exponential_factorial(5), None);
+/// ```
+pub fn exponential_factorial(n: u64) -> Option<BigUint> {
+    SMALL_EXPONENTIAL_FACTORIAL
+        .get(n as usize)
+        .map(|&value| BigUint::from(value))
+}
+
+/// Returns the number of decimal digits of the exponential factorial `n$`,
+/// or `None` if the digit count itself doesn't fit in a `u64`, which is the
+/// case from `n = 6` on.
+///
+/// The digit count follows from `log10(n$) = (n - 1)$ log10(n)`.
+///
+/// # Examples
+/// ```
+/// use factorial::exponential_factorial_digit_count;
+/// assert_eq!(exponential_factorial_digit_count(4), Some(6));
+/// assert_eq!(exponential_factorial_digit_count(5), Some(183231));
+/// assert_eq!(exponential_factorial_digit_count(6), None);
+/// ```
+pub fn exponential_factorial_digit_count(n: u64) -> Option<u64> {
+    match n {
+        0 | 1 => Some(1),
+        2..=5 => {
+            let exponent = f64::from(SMALL_EXPONENTIAL_FACTORIAL[n as usize - 1]);
+            Some((exponent * (n as f64).log10()).floor() as u64 + 1)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyperfactorial_digits() {
+        use crate::Hyperfactorial;
+        for n in 0..=150u64 {
+            let digits = BigUint::from(n).hyperfactorial().to_string().len() as u64;
+            assert_eq!(hyperfactorial_digit_count(n), digits, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn exponential_factorial_small() {
+        // n$ = n^((n - 1)$)
+        let mut prev = BigUint::from(1u32);
+        for n in 1..=4u32 {
+            let expected = num_traits::pow(BigUint::from(n), prev.to_string().parse().unwrap());
+            assert_eq!(exponential_factorial(u64::from(n)), Some(expected.clone()));
+            prev = expected;
+        }
+        assert_eq!(exponential_factorial(0), Some(BigUint::from(1u32)));
+        assert_eq!(exponential_factorial(4), Some(BigUint::from(262_144u32)));
+    }
+
+    #[test]
+    fn exponential_factorial_rejects_large() {
+        assert_eq!(exponential_factorial(5), None);
+        assert_eq!(exponential_factorial(u64::MAX), None);
+    }
+
+    #[test]
+    fn exponential_factorial_digits() {
+        for n in 0..=4 {
+            let digits = exponential_factorial(n).unwrap().to_string().len() as u64;
+            assert_eq!(exponential_factorial_digit_count(n), Some(digits));
+        }
+        let five = num_traits::pow(BigUint::from(5u32), 262_144);
+        assert_eq!(
+            exponential_factorial_digit_count(5),
+            Some(five.to_string().len() as u64)
+        );
+        assert_eq!(exponential_factorial_digit_count(6), None);
+    }
+
+    #[test]
+    fn subfactorial_small() {
+        let expected = [1u64, 0, 1, 2, 9, 44, 265, 1854, 14833, 133496, 1334961];
+        for (n, &d) in expected.iter().enumerate() {
+            assert_eq!(checked_subfactorial(&(n as u64)), Some(d));
+        }
+        assert_eq!(checked_subfactorial(&100u64), None);
+    }
+
+    const GLAISHER_KINKELIN: f64 = 1.282_427_129_100_622_6;
+
+    #[test]
+    fn hyperfactorial_ratio_small() {
+        // H(1) = 1, so the ratio is e^(1/4).
+        assert!((hyperfactorial_ratio(1) - 0.25f64.exp()).abs() < 1e-12);
+        // H(2) = 4
+        let expected = 4.0 / 2f64.powf(4.0 / 2.0 + 1.0 + 1.0 / 12.0) * 1f64.exp();
+        assert!((hyperfactorial_ratio(2) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hyperfactorial_ratio_converges() {
+        let mut last_err = f64::INFINITY;
+        for n in [10, 100, 1000] {
+            let err = (hyperfactorial_ratio(n) - GLAISHER_KINKELIN).abs();
+            assert!(err < last_err, "no improvement at n = {n}");
+            last_err = err;
+        }
+        assert!(last_err < 1e-6);
+    }
+
+    #[test]
+    fn hyperfactorial_ratio_zero() {
+        assert!(hyperfactorial_ratio(0).is_nan());
+    }
+}
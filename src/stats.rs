@@ -0,0 +1,210 @@
+//! Floating point adapters mirroring the factorial and gamma functions of
+//! `statrs`.
+//!
+//! The functions in this module have the same signatures as their
+//! counterparts in `statrs::function`, so they can be swapped into existing
+//! statistics code:
+//!
+//! | `statrs`                                    | this crate           |
+//! |---------------------------------------------|----------------------|
+//! | `statrs::function::factorial::factorial`    | [`factorial_f64`]    |
+//! | `statrs::function::factorial::ln_factorial` | [`ln_factorial_f64`] |
+//! | `statrs::function::gamma::gamma`            | [`gamma_f64`]        |
+//!
+//! Integer arguments are answered from the exact factorial, rounded once to
+//! the nearest `f64`, while non-integer arguments fall back to this crate's
+//! gamma function. [`binomial_pmf`] goes one step further and evaluates the
+//! whole probability mass exactly before rounding.
+
+use crate::combinatorics::checked_binomial;
+use crate::estimate::ln_factorial;
+use crate::gamma::gamma;
+use crate::Factorial;
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive};
+
+/// Largest `n` for which `n!` is finite as an `f64`.
+const MAX_F64_FACTORIAL: u64 = 170;
+
+/// Returns `x!` as the nearest `f64`, or infinity if it is too large.
+///
+/// Unlike a table of repeated floating point multiplications, the exact
+/// factorial is rounded only once.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_f64;
+/// assert_eq!(factorial_f64(10), 3628800.0);
+/// assert_eq!(factorial_f64(171), f64::INFINITY);
+/// ```
+pub fn factorial_f64(x: u64) -> f64 {
+    if x > MAX_F64_FACTORIAL {
+        return f64::INFINITY;
+    }
+    BigUint::from(x)
+        .factorial()
+        .to_f64()
+        .unwrap_or(f64::INFINITY)
+}
+
+/// Returns `x!` as an `f32`, or infinity beyond `34!`, the largest factorial
+/// below `f32::MAX`.
+///
+/// Only up to `13!` is the result exact; beyond that it is [`factorial_f64`]
+/// narrowed to `f32`, so there's no overflow on the way to the limit.
+///
+/// # Examples
+/// ```
+/// use factorial::factorial_f32;
+/// assert_eq!(factorial_f32(10), 3628800.0);
+/// assert_eq!(factorial_f32(35), f32::INFINITY);
+/// ```
+pub fn factorial_f32(x: u64) -> f32 {
+    factorial_f64(x) as f32
+}
+
+/// Returns `ln(x!)`.
+///
+/// Small arguments use the exact factorial, larger ones Stirling's series,
+/// which is accurate to the precision of an `f64` there.
+///
+/// # Examples
+/// ```
+/// use factorial::ln_factorial_f64;
+/// assert!((ln_factorial_f64(5) - 120f64.ln()).abs() < 1e-14);
+/// ```
+pub fn ln_factorial_f64(x: u64) -> f64 {
+    ln_factorial(x)
+}
+
+/// Returns `Γ(x)`.
+///
+/// Positive integers are answered exactly through [`factorial_f64`], other
+/// arguments use [`gamma`](crate::gamma).
+///
+/// # Examples
+/// ```
+/// use factorial::gamma_f64;
+/// assert_eq!(gamma_f64(6.0), 120.0);
+/// assert!((gamma_f64(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-10);
+/// ```
+pub fn gamma_f64(x: f64) -> f64 {
+    if x >= 1.0 && x == x.floor() {
+        return factorial_f64((x as u64).saturating_sub(1));
+    }
+    gamma(x)
+}
+
+/// Returns the probability `C(n, k) p^k (1 - p)^(n - k)` of exactly `k`
+/// successes in `n` Bernoulli trials with success probability `p`.
+///
+/// The probability is evaluated as an exact fraction and rounded only at the
+/// end, where the usual floating point approach of exponentiating a sum of
+/// logarithms loses several digits for moderate `n`. The cost grows with `n`,
+/// as the exact fraction has about `53 n` bits.
+///
+/// Returns `NaN` if `p` is not within `[0, 1]`.
+///
+/// # Examples
+/// ```
+/// use factorial::binomial_pmf;
+/// assert_eq!(binomial_pmf(4, 2, 0.5), 0.375);
+/// assert_eq!(binomial_pmf(4, 5, 0.5), 0.0);
+/// ```
+pub fn binomial_pmf(n: u64, k: u64, p: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if k > n {
+        return 0.0;
+    }
+    let Some(p_exact) = BigRational::from_float(p) else {
+        return f64::NAN;
+    };
+    let q_exact = BigRational::one() - &p_exact;
+    let binomial =
+        checked_binomial(&BigUint::from(n), &BigUint::from(k)).expect("BigUint never overflows");
+    let pmf = BigRational::from_integer(BigInt::from(binomial))
+        * num_traits::pow(p_exact, k as usize)
+        * num_traits::pow(q_exact, (n - k) as usize);
+    pmf.to_f64().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel_err(actual: f64, expected: &BigRational) -> f64 {
+        let actual = BigRational::from_float(actual).unwrap();
+        ((actual - expected) / expected).to_f64().unwrap().abs()
+    }
+
+    #[test]
+    fn factorial_f64_matches_exact() {
+        for x in 0..=MAX_F64_FACTORIAL {
+            let exact = BigRational::from_integer(BigUint::from(x).factorial().into());
+            assert!(rel_err(factorial_f64(x), &exact) <= f64::EPSILON / 2.0);
+        }
+        assert_eq!(factorial_f64(MAX_F64_FACTORIAL + 1), f64::INFINITY);
+    }
+
+    #[test]
+    fn factorial_f32_narrows_f64() {
+        for x in 0..=10 {
+            assert_eq!(f64::from(factorial_f32(x)), factorial_f64(x));
+        }
+        for x in 0..=13u64 {
+            let exact = BigUint::from(x).factorial().to_f32().unwrap();
+            assert_eq!(factorial_f32(x), exact);
+        }
+        let largest = factorial_f32(34);
+        assert!(largest.is_finite());
+        assert!((f64::from(largest) / factorial_f64(34) - 1.0).abs() < 1e-7);
+        for x in [35, 170, 171, u64::MAX] {
+            assert_eq!(factorial_f32(x), f32::INFINITY);
+        }
+    }
+
+    #[test]
+    fn gamma_f64_integers_and_halves() {
+        for x in 1..=20u64 {
+            assert_eq!(gamma_f64(x as f64), factorial_f64(x - 1));
+        }
+        assert!(gamma_f64(0.0).is_nan());
+        assert!((gamma_f64(1.5) - std::f64::consts::PI.sqrt() / 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn binomial_pmf_more_accurate_than_logarithms() {
+        let (n, k, p) = (1000u64, 400u64, 0.4f64);
+        let p_exact = BigRational::from_float(p).unwrap();
+        let q_exact = BigRational::one() - &p_exact;
+        let binomial = checked_binomial(&BigUint::from(n), &BigUint::from(k)).unwrap();
+        let exact = BigRational::from_integer(binomial.into())
+            * num_traits::pow(p_exact, k as usize)
+            * num_traits::pow(q_exact, (n - k) as usize);
+
+        // What a floating point library computes.
+        let ln_binomial = ln_factorial_f64(n) - ln_factorial_f64(k) - ln_factorial_f64(n - k);
+        let float_pmf = (ln_binomial + k as f64 * p.ln() + (n - k) as f64 * (1.0 - p).ln()).exp();
+
+        let exact_err = rel_err(binomial_pmf(n, k, p), &exact);
+        let float_err = rel_err(float_pmf, &exact);
+        assert!(exact_err <= f64::EPSILON / 2.0);
+        assert!(exact_err < float_err);
+    }
+
+    #[test]
+    fn binomial_pmf_sums_to_one() {
+        let total: f64 = (0..=50).map(|k| binomial_pmf(50, k, 0.3)).sum();
+        assert!((total - 1.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn binomial_pmf_rejects_bad_probability() {
+        assert!(binomial_pmf(10, 5, -0.1).is_nan());
+        assert!(binomial_pmf(10, 5, 1.5).is_nan());
+        assert!(binomial_pmf(10, 5, f64::NAN).is_nan());
+    }
+}
@@ -0,0 +1,93 @@
+//! The building blocks of the prime swing algorithm.
+//!
+//! The swing factorial of `n` is `n≀ = n! / (⌊n / 2⌋!)^2`, which for even `n`
+//! is the central binomial coefficient `C(n, n / 2)`. Writing `n!` as
+//! `2^e m` with `m` odd, the odd factorial `m` satisfies
+//! `m(n) = m(⌊n / 2⌋)^2 · odd(n≀)`, and `n!` follows with a single shift.
+//! Every prime power dividing `n≀` is at most `n`, so the swing factorial is
+//! cheap to build from a sieve, which is what makes the algorithm fast.
+
+use crate::biguint;
+use num_bigint::BigUint;
+use primal_sieve::Sieve;
+
+/// Returns the swing factorial `n! / (⌊n / 2⌋!)^2`, or `None` if the sieve
+/// doesn't cover `n`.
+///
+/// # Examples
+/// ```
+/// use factorial::swing;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(100);
+/// // 7! / (3!)^2
+/// assert_eq!(swing(7, &sieve), Some(140u32.into()));
+/// // The central binomial coefficient C(10, 5)
+/// assert_eq!(swing(10, &sieve), Some(252u32.into()));
+/// ```
+pub fn swing(n: usize, sieve: &Sieve) -> Option<BigUint> {
+    if sieve.upper_bound() < n {
+        return None;
+    }
+    // The exponent of two in n! / (n / 2)!^2 is the number of ones in n / 2.
+    Some(biguint::prime_swing::<BigUint>(n, sieve) << (n / 2).count_ones())
+}
+
+/// Returns the odd factorial of `n`, the largest odd divisor of `n!`, or
+/// `None` if the sieve doesn't cover `n`.
+///
+/// `n!` is the odd factorial shifted left by `n - popcount(n)`, see
+/// [`Factorial::factorial_two_exponent`](crate::Factorial::factorial_two_exponent).
+///
+/// # Examples
+/// ```
+/// use factorial::odd_factorial;
+/// use primal_sieve::Sieve;
+/// let sieve = Sieve::new(100);
+/// // 10! = 2^8 * 14175
+/// assert_eq!(odd_factorial(10, &sieve), Some(14175u32.into()));
+/// ```
+pub fn odd_factorial(n: usize, sieve: &Sieve) -> Option<BigUint> {
+    if sieve.upper_bound() < n {
+        return None;
+    }
+    Some(biguint::odd_factorial(n, sieve))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Binomial, Factorial};
+
+    #[test]
+    fn swing_is_factorial_ratio() {
+        let sieve = Sieve::new(2000);
+        for n in (0..300usize).chain([1000, 1999, 2000]) {
+            let expected =
+                BigUint::from(n).factorial() / num_traits::pow(BigUint::from(n / 2).factorial(), 2);
+            assert_eq!(swing(n, &sieve), Some(expected), "n = {n}");
+        }
+        for n in 0..500u32 {
+            let central = BigUint::from(2 * n).binomial(&BigUint::from(n));
+            assert_eq!(swing(2 * n as usize, &sieve), Some(central));
+        }
+    }
+
+    #[test]
+    fn odd_factorial_is_odd_part() {
+        let sieve = Sieve::new(2000);
+        for n in (0..300usize).chain([1000, 1999, 2000]) {
+            let odd = odd_factorial(n, &sieve).unwrap();
+            assert!(odd.bit(0));
+            let shift = (n as u64).factorial_two_exponent();
+            assert_eq!(odd << shift, BigUint::from(n).factorial(), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn sieve_too_small() {
+        let sieve = Sieve::new(100);
+        let n = sieve.upper_bound() + 1;
+        assert_eq!(swing(n, &sieve), None);
+        assert_eq!(odd_factorial(n, &sieve), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Factorials stored in the smallest type that holds them.
+
+use crate::{array, Factorial};
+use num_bigint::BigUint;
+use std::fmt;
+
+/// Computes `n!` as a [`FactorialValue`] in the smallest type that holds it.
+///
+/// # Examples
+/// ```
+/// use factorial::FactorialValue;
+/// assert_eq!(factorial::factorial!(5), FactorialValue::U8(120));
+/// assert!(matches!(factorial::factorial!(50), FactorialValue::Big(_)));
+/// ```
+#[macro_export]
+macro_rules! factorial {
+    ($n:expr) => {
+        $crate::FactorialValue::new($n)
+    };
+}
+
+/// The value of a factorial, stored in the smallest unsigned type that can
+/// hold it.
+///
+/// Only factorials that don't fit in a `u128` are stored as a [`BigUint`], so
+/// small results stay cheap to copy and compare.
+///
+/// # Examples
+/// ```
+/// use factorial::FactorialValue;
+/// assert_eq!(FactorialValue::new(8), FactorialValue::U16(40320));
+/// assert_eq!(FactorialValue::new(20).to_biguint(), 2432902008176640000u64.into());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FactorialValue {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Big(BigUint),
+}
+
+impl FactorialValue {
+    /// Computes `n!`.
+    pub fn new(n: usize) -> Self {
+        let Some(&value) = array::SMALL_FACTORIAL.get(n) else {
+            return FactorialValue::Big(BigUint::from(n).factorial());
+        };
+        if let Ok(value) = u8::try_from(value) {
+            FactorialValue::U8(value)
+        } else if let Ok(value) = u16::try_from(value) {
+            FactorialValue::U16(value)
+        } else if let Ok(value) = u32::try_from(value) {
+            FactorialValue::U32(value)
+        } else if let Ok(value) = u64::try_from(value) {
+            FactorialValue::U64(value)
+        } else {
+            FactorialValue::U128(value)
+        }
+    }
+
+    /// Returns the value as a [`BigUint`], whatever type it is stored in.
+    pub fn to_biguint(&self) -> BigUint {
+        match self {
+            FactorialValue::U8(value) => BigUint::from(*value),
+            FactorialValue::U16(value) => BigUint::from(*value),
+            FactorialValue::U32(value) => BigUint::from(*value),
+            FactorialValue::U64(value) => BigUint::from(*value),
+            FactorialValue::U128(value) => BigUint::from(*value),
+            FactorialValue::Big(value) => value.clone(),
+        }
+    }
+}
+
+impl From<FactorialValue> for BigUint {
+    fn from(value: FactorialValue) -> BigUint {
+        match value {
+            FactorialValue::Big(value) => value,
+            small => small.to_biguint(),
+        }
+    }
+}
+
+impl fmt::Display for FactorialValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FactorialValue::U8(value) => value.fmt(f),
+            FactorialValue::U16(value) => value.fmt(f),
+            FactorialValue::U32(value) => value.fmt(f),
+            FactorialValue::U64(value) => value.fmt(f),
+            FactorialValue::U128(value) => value.fmt(f),
+            FactorialValue::Big(value) => value.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smallest_variants() {
+        assert_eq!(factorial!(0), FactorialValue::U8(1));
+        assert_eq!(factorial!(5), FactorialValue::U8(120));
+        assert_eq!(factorial!(6), FactorialValue::U16(720));
+        assert_eq!(factorial!(9), FactorialValue::U32(362_880));
+        assert_eq!(factorial!(13), FactorialValue::U64(6_227_020_800));
+        assert_eq!(
+            factorial!(21),
+            FactorialValue::U128(51_090_942_171_709_440_000)
+        );
+        assert_eq!(
+            factorial!(34),
+            FactorialValue::U128(array::SMALL_FACTORIAL[34])
+        );
+        assert!(matches!(factorial!(35), FactorialValue::Big(_)));
+    }
+
+    #[test]
+    fn values_match_factorial() {
+        for n in 0..100usize {
+            let value = factorial!(n);
+            let expected = BigUint::from(n).factorial();
+            assert_eq!(value.to_string(), expected.to_string());
+            assert_eq!(BigUint::from(value), expected, "mismatch for {n}");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Factorials with wrapping arithmetic.
+
+use num_traits::{AsPrimitive, One, WrappingMul, Zero};
+
+/// Returns `n!` computed with wrapping multiplication, so it never fails.
+///
+/// The result is only meaningful modulo `2^bits` of the type `T`, i.e. it is
+/// `n! mod 2^bits`. As `n!` is divisible by `2^(n - popcount(n))`, the result
+/// is zero for all `n` beyond a few times the number of bits.
+///
+/// # Examples
+/// ```
+/// use factorial::wrapping_factorial;
+/// // 6! = 720 = 2 * 256 + 208
+/// assert_eq!(wrapping_factorial::<u8>(6), 208);
+/// assert_eq!(wrapping_factorial::<u64>(100), 0);
+/// ```
+pub fn wrapping_factorial<T>(n: usize) -> T
+where
+    T: WrappingMul + One + Zero + Copy + 'static,
+    usize: AsPrimitive<T>,
+{
+    let mut acc = T::one();
+    for i in 2..=n {
+        if acc.is_zero() {
+            break;
+        }
+        acc = acc.wrapping_mul(&i.as_());
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Factorial;
+
+    #[test]
+    fn wrapping_u8() {
+        assert_eq!(wrapping_factorial::<u8>(5), 120);
+        assert_eq!(wrapping_factorial::<u8>(6), 208);
+        assert_eq!(wrapping_factorial::<u8>(1000), 0);
+    }
+
+    #[test]
+    fn wrapping_matches_reduced_factorial() {
+        for n in 0..=34usize {
+            let exact = (n as u128).factorial();
+            assert_eq!(wrapping_factorial::<u32>(n), exact as u32);
+            assert_eq!(wrapping_factorial::<u64>(n), exact as u64);
+            assert_eq!(wrapping_factorial::<u128>(n), exact);
+        }
+    }
+
+    #[test]
+    fn wrapping_signed() {
+        // 6! mod 256 = 208, which is -48 as an i8
+        assert_eq!(wrapping_factorial::<i8>(6), -48);
+    }
+}